@@ -0,0 +1,166 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, GenericParam, ItemEnum};
+
+use crate::{case::to_snake_case, variant::Variant};
+
+/// Generates an `is_*()` predicate for every variant, and `as_*()`, `as_*_mut()` and `into_*()`
+/// accessors for every variant holding a single payload.
+pub(crate) fn accessors(item: &ItemEnum) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let methods = item.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+		let name = to_snake_case(&variant_ident.to_string());
+		let predicate = format_ident!("is_{}", name);
+		let doc = format!("Returns `true` if this is the `{}` variant.", variant_ident);
+		let mut tokens = quote! {
+			#[doc = #doc]
+			pub fn #predicate(&self) -> bool {
+				::core::matches!(self, Self::#variant_ident { .. })
+			}
+		};
+
+		let Fields::Unnamed(fields) = &variant.fields else {
+			return tokens;
+		};
+
+		if fields.unnamed.len() != 1 {
+			return tokens;
+		}
+
+		let ty = &fields.unnamed[0].ty;
+		let as_ref = format_ident!("as_{}", name);
+		let as_mut = format_ident!("as_{}_mut", name);
+		let into = format_ident!("into_{}", name);
+		let as_ref_doc = format!(
+			"Returns a reference to the payload of the `{}` variant, if this is that variant.",
+			variant_ident
+		);
+		let as_mut_doc = format!(
+			"Returns a mutable reference to the payload of the `{}` variant, if this is that variant.",
+			variant_ident
+		);
+		let into_doc = format!(
+			"Returns the payload of the `{}` variant, or `self` if this is another variant.",
+			variant_ident
+		);
+
+		tokens.extend(quote! {
+			#[doc = #as_ref_doc]
+			pub fn #as_ref(&self) -> ::core::option::Option<&#ty> {
+				match self {
+					Self::#variant_ident(value) => ::core::option::Option::Some(value),
+					#[allow(unreachable_patterns)]
+					_ => ::core::option::Option::None,
+				}
+			}
+
+			#[doc = #as_mut_doc]
+			pub fn #as_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+				match self {
+					Self::#variant_ident(value) => ::core::option::Option::Some(value),
+					#[allow(unreachable_patterns)]
+					_ => ::core::option::Option::None,
+				}
+			}
+
+			#[doc = #into_doc]
+			pub fn #into(self) -> ::core::result::Result<#ty, Self> {
+				match self {
+					Self::#variant_ident(value) => ::core::result::Result::Ok(value),
+					#[allow(unreachable_patterns)]
+					_ => ::core::result::Result::Err(self),
+				}
+			}
+		});
+
+		tokens
+	});
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#(#methods)*
+		}
+	}
+}
+
+/// Generates `From` for the enum from every discovered payload type.
+pub(crate) fn from(item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let impls = variants.iter().map(|variant| {
+		let name = &variant.name;
+		let payload = variant.payload();
+		// Registered types stored as another type are converted to it first.
+		let registered = variant.args.store.as_ref().map(|_| {
+			let registered = variant.registered();
+			let mut generics = item.generics.clone();
+
+			generics
+				.params
+				.extend(variant.generics.params.iter().cloned());
+
+			let mut params: Vec<GenericParam> = generics.params.into_iter().collect();
+
+			params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+			generics.params = params.into_iter().collect();
+
+			if let Some(where_clause) = &variant.generics.where_clause {
+				generics
+					.make_where_clause()
+					.predicates
+					.extend(where_clause.predicates.iter().cloned());
+			}
+
+			let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+			quote! {
+				impl #impl_generics ::core::convert::From<#registered> for #ident #ty_generics #where_clause {
+					fn from(value: #registered) -> Self {
+						Self::#name(::core::convert::From::from(value))
+					}
+				}
+			}
+		});
+
+		quote! {
+			impl #impl_generics ::core::convert::From<#payload> for #ident #ty_generics #where_clause {
+				fn from(value: #payload) -> Self {
+					Self::#name(value)
+				}
+			}
+
+			#registered
+		}
+	});
+
+	quote!(#(#impls)*)
+}
+
+/// Generates `TryFrom<Enum>` for every discovered payload type, returning the enum unchanged when
+/// it holds another variant.
+pub(crate) fn try_from(item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let impls = variants.iter().map(|variant| {
+		let name = &variant.name;
+		let payload = variant.payload();
+
+		quote! {
+			impl #impl_generics ::core::convert::TryFrom<#ident #ty_generics> for #payload #where_clause {
+				type Error = #ident #ty_generics;
+
+				fn try_from(value: #ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+					match value {
+						#ident::#name(value) => ::core::result::Result::Ok(value),
+						#[allow(unreachable_patterns)]
+						_ => ::core::result::Result::Err(value),
+					}
+				}
+			}
+		}
+	});
+
+	quote!(#(#impls)*)
+}
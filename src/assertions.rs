@@ -0,0 +1,58 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{ItemEnum, Token, TypeParamBound, punctuated::Punctuated};
+
+/// Generates static assertions that every payload in `payloads` satisfies `bounds`, described as
+/// `description`, failing with an error naming the offending type.
+fn assert_bounds(
+	item: &ItemEnum,
+	bounds: TokenStream,
+	description: &str,
+	payloads: &[TokenStream],
+) -> TokenStream {
+	let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+	let message = format!(
+		"`{{Self}}` doesn't implement `{}`, which every variant of `{}` must",
+		description, item.ident
+	);
+
+	quote! {
+		const _: () = {
+			#[diagnostic::on_unimplemented(message = #message)]
+			trait MustImpl: #bounds {}
+
+			impl<T: ?::core::marker::Sized + #bounds> MustImpl for T {}
+
+			fn assert_impl<T: ?::core::marker::Sized + MustImpl>() {}
+
+			#[allow(dead_code)]
+			fn assert_all #impl_generics () #where_clause {
+				#(assert_impl::<#payloads>();)*
+			}
+		};
+	}
+}
+
+/// Generates static assertions that every payload in `payloads` satisfies `bounds`, failing with
+/// an error naming the offending type.
+pub(crate) fn must_impl(
+	item: &ItemEnum,
+	bounds: &Punctuated<TypeParamBound, Token![+]>,
+	payloads: &[TokenStream],
+) -> TokenStream {
+	let bounds = bounds.to_token_stream();
+	let description = bounds.to_string();
+
+	assert_bounds(item, bounds, &description, payloads)
+}
+
+/// Generates static assertions that every payload in `payloads` is `Send` and `Sync`, failing with
+/// an error naming the offending type.
+pub(crate) fn send_sync(item: &ItemEnum, payloads: &[TokenStream]) -> TokenStream {
+	assert_bounds(
+		item,
+		quote!(::core::marker::Send + ::core::marker::Sync),
+		"Send + Sync",
+		payloads,
+	)
+}
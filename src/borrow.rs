@@ -0,0 +1,122 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, GenericParam, ItemEnum, Lifetime, parse_quote};
+
+use crate::fields::{destructure, matched_self};
+
+/// Generates the companion enum holding a reference to the fields of each variant, and the
+/// `as_ref()` method borrowing the enum as it.
+pub(crate) fn as_ref(item: &ItemEnum) -> TokenStream {
+	companion(item, false)
+}
+
+/// Generates the companion enum holding a mutable reference to the fields of each variant, and the
+/// `as_mut()` method borrowing the enum as it.
+pub(crate) fn as_mut(item: &ItemEnum) -> TokenStream {
+	companion(item, true)
+}
+
+fn companion(item: &ItemEnum, mutable: bool) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let (ref_ident, method, reference, derive) = if mutable {
+		(
+			format_ident!("{}Mut", ident),
+			format_ident!("as_mut"),
+			quote!(&mut self),
+			None,
+		)
+	} else {
+		(
+			format_ident!("{}Ref", ident),
+			format_ident!("as_ref"),
+			quote!(&self),
+			Some(quote!(#[derive(::core::clone::Clone, ::core::marker::Copy)])),
+		)
+	};
+	let mutability = mutable.then(|| quote!(mut));
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let taken = item
+		.generics
+		.lifetimes()
+		.any(|param| param.lifetime.ident == "a");
+	let lifetime: Lifetime = if taken {
+		parse_quote!('__a)
+	} else {
+		parse_quote!('a)
+	};
+	let mut ref_generics = item.generics.clone();
+	let mut elided_generics = item.generics.clone();
+
+	// The lifetime would be unused without any field to borrow.
+	if item
+		.variants
+		.iter()
+		.any(|variant| !variant.fields.is_empty())
+	{
+		ref_generics
+			.params
+			.insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+		elided_generics
+			.params
+			.insert(0, GenericParam::Lifetime(parse_quote!('_)));
+	}
+
+	let (_, elided_ty_generics, _) = elided_generics.split_for_impl();
+	let doc = format!(
+		"Borrows the fields of a [`{}`] variant, returned by `{}::{}()`.",
+		ident, ident, method
+	);
+	let method_doc = if mutable {
+		"Mutably borrows the fields of this variant."
+	} else {
+		"Borrows the fields of this variant."
+	};
+	let mut variants = vec![];
+	let mut arms = vec![];
+
+	for variant in &item.variants {
+		let variant_ident = &variant.ident;
+		let (pattern, _) = destructure(&variant.fields);
+		let fields = match &variant.fields {
+			Fields::Named(named) => {
+				let fields = named.named.iter().map(|field| {
+					let field_ident = &field.ident;
+					let ty = &field.ty;
+
+					quote!(#field_ident: &#lifetime #mutability #ty)
+				});
+
+				quote!({ #(#fields),* })
+			}
+			Fields::Unnamed(unnamed) => {
+				let types = unnamed.unnamed.iter().map(|field| &field.ty);
+
+				quote!((#(&#lifetime #mutability #types),*))
+			}
+			Fields::Unit => TokenStream::new(),
+		};
+
+		variants.push(quote!(#variant_ident #fields));
+		arms.push(quote!(Self::#variant_ident #pattern => #ref_ident::#variant_ident #pattern));
+	}
+
+	let matched = matched_self(arms.is_empty());
+
+	quote! {
+		#[doc = #doc]
+		#derive
+		#vis enum #ref_ident #ref_generics #where_clause {
+			#(#variants),*
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#[doc = #method_doc]
+			pub fn #method(#reference) -> #ref_ident #elided_ty_generics {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,107 @@
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+};
+
+use quote::ToTokens;
+use syn::{File, Item, parse_file};
+
+use crate::{
+	expand::stable_hash, options::Options, scan::ENUM_BUILDER_DECL, variant::ENUM_BUILDER_VARIANT,
+};
+
+/// The directory scan results are cached in with the `cache` parameter: the provided path relative
+/// to the crate root, or `enum_builder/cache` in the target directory.
+pub(crate) fn dir(options: &Options) -> Option<PathBuf> {
+	let cache = options.cache.as_ref()?;
+	let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+
+	Some(match cache {
+		Some(path) => manifest_dir.join(path),
+		None => env::var_os("CARGO_TARGET_DIR")
+			.map_or_else(|| manifest_dir.join("target"), PathBuf::from)
+			.join("enum_builder")
+			.join("cache"),
+	})
+}
+
+/// The file `path` is cached in under `dir`.
+fn entry(dir: &Path, path: &Path) -> PathBuf {
+	dir.join(format!("{:016x}.rs", stable_hash(&path.to_string_lossy())))
+}
+
+/// Keeps the items of `syntax` scanning looks at, without the bodies of functions and impls, which
+/// make up most of the parsing work.
+fn skeleton(syntax: &File) -> File {
+	let items = syntax
+		.items
+		.iter()
+		.filter_map(|item| match item {
+			Item::Struct(_) | Item::Enum(_) | Item::Union(_) | Item::Type(_) | Item::Trait(_) => {
+				Some(item.clone())
+			}
+			// Inherent impls are only scanned for registrations of the types they're for.
+			Item::Impl(item)
+				if item.trait_.is_some()
+					|| item.attrs.iter().any(|attr| {
+						attr.path()
+							.segments
+							.last()
+							.is_some_and(|segment| segment.ident == ENUM_BUILDER_VARIANT)
+					}) =>
+			{
+				let mut item = item.clone();
+
+				item.items.clear();
+				Some(Item::Impl(item))
+			}
+			Item::Macro(item)
+				if item
+					.mac
+					.path
+					.segments
+					.last()
+					.is_some_and(|segment| segment.ident == ENUM_BUILDER_DECL) =>
+			{
+				Some(Item::Macro(item.clone()))
+			}
+			_ => None,
+		})
+		.collect();
+
+	File {
+		shebang: None,
+		attrs: vec![],
+		items,
+	}
+}
+
+/// The first line of a cached skeleton, identifying the sources hashing to `hash` it was parsed
+/// from and the edition they were parsed as, as 2015 sources are parsed differently.
+fn header(hash: u64, edition: Option<&str>) -> String {
+	format!("// {:016x} {}", hash, edition.unwrap_or("default"))
+}
+
+/// Loads the skeleton cached for `path` under `dir`, if it was cached from sources hashing to
+/// `hash` parsed as `edition`.
+pub(crate) fn load(dir: &Path, path: &Path, hash: u64, edition: Option<&str>) -> Option<File> {
+	let cached = fs::read_to_string(entry(dir, path)).ok()?;
+	let (cached_header, skeleton) = cached.split_once('\n')?;
+
+	if cached_header != header(hash, edition) {
+		return None;
+	}
+
+	parse_file(skeleton).ok()
+}
+
+/// Caches the skeleton of `syntax`, parsed from `path` with sources hashing to `hash` as `edition`,
+/// under `dir`. Failing to write the cache only makes the next scan slower, so errors are ignored.
+pub(crate) fn store(dir: &Path, path: &Path, hash: u64, edition: Option<&str>, syntax: &File) {
+	let cached = format!(
+		"{}\n{}",
+		header(hash, edition),
+		skeleton(syntax).into_token_stream()
+	);
+	let _ = fs::create_dir_all(dir).and_then(|_| fs::write(entry(dir, path), cached));
+}
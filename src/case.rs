@@ -0,0 +1,76 @@
+/// Splits a `PascalCase` or `snake_case` identifier into lowercase words.
+fn words(name: &str) -> Vec<String> {
+	let chars: Vec<char> = name.chars().collect();
+	let mut words = vec![];
+	let mut word = String::new();
+
+	for (index, &c) in chars.iter().enumerate() {
+		if c == '_' || c == '-' {
+			if !word.is_empty() {
+				words.push(std::mem::take(&mut word));
+			}
+
+			continue;
+		}
+
+		if c.is_uppercase() && !word.is_empty() {
+			let prev = chars[index - 1];
+			let next_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+
+			if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+				words.push(std::mem::take(&mut word));
+			}
+		}
+
+		word.extend(c.to_lowercase());
+	}
+
+	if !word.is_empty() {
+		words.push(word);
+	}
+
+	words
+}
+
+/// Converts an identifier to `snake_case`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+	words(name).join("_")
+}
+
+/// Converts an identifier to `kebab-case`.
+pub(crate) fn to_kebab_case(name: &str) -> String {
+	words(name).join("-")
+}
+
+/// Converts an identifier to `SCREAMING_SNAKE_CASE`.
+pub(crate) fn to_screaming_snake_case(name: &str) -> String {
+	to_snake_case(name).to_uppercase()
+}
+
+/// Case conventions variant names can be renamed to, named like serde's `rename_all`.
+pub(crate) const CONVENTIONS: &[&str] = &["snake_case", "kebab-case", "SCREAMING_SNAKE_CASE"];
+
+/// Renames an identifier following one of the [`CONVENTIONS`], or keeps it if none is provided.
+pub(crate) fn rename(name: &str, convention: Option<&str>) -> String {
+	match convention {
+		Some("snake_case") => to_snake_case(name),
+		Some("kebab-case") => to_kebab_case(name),
+		Some("SCREAMING_SNAKE_CASE") => to_screaming_snake_case(name),
+		_ => name.to_owned(),
+	}
+}
+
+/// Converts an identifier to `PascalCase`.
+pub(crate) fn to_pascal_case(name: &str) -> String {
+	words(name)
+		.iter()
+		.map(|word| {
+			let mut chars = word.chars();
+
+			match chars.next() {
+				Some(first) => first.to_uppercase().chain(chars).collect(),
+				None => String::new(),
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,142 @@
+use std::{
+	env,
+	path::{Path, PathBuf},
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+use crate::{
+	options::Options,
+	scan::{self, Registration},
+};
+
+const ENUM_BUILDER_CHECK: &str = "enum_builder_check";
+
+/// The file invoking [macro@crate::enum_builder_check], which is `local_file`, the file of the
+/// macro call site, when the compiler exposes it. Otherwise it's found by scanning the crate under
+/// `CARGO_MANIFEST_DIR` for the invocation, which must then be the only one.
+pub(crate) fn call_site_file(local_file: Option<PathBuf>) -> syn::Result<PathBuf> {
+	if let Some(file) = local_file {
+		return Ok(file);
+	}
+
+	let mut files = match env::var_os("CARGO_MANIFEST_DIR") {
+		Some(dir) => {
+			scan::find_macro_calls(ENUM_BUILDER_CHECK, Path::new(&dir), &Options::default())?
+		}
+		None => vec![],
+	};
+
+	if files.len() == 1 {
+		return Ok(files.remove(0));
+	}
+
+	Err(syn::Error::new(
+		Span::call_site(),
+		format!(
+			"unable to determine the file invoking {}, as the compiler doesn't expose it and {} \
+			 source files under CARGO_MANIFEST_DIR invoke it",
+			ENUM_BUILDER_CHECK,
+			if files.is_empty() {
+				"no".to_owned()
+			} else {
+				files.len().to_string()
+			}
+		),
+	))
+}
+
+/// Describes each of `registrations` for an enum that no enum_builder enum in the crate builds,
+/// other than `built`, the enum being built if any, which may be declared outside the crate's
+/// sources. The crate is scanned from `CARGO_MANIFEST_DIR`, or from `root` when it's unknown, and
+/// only when some registration isn't for `built`.
+fn unknown(
+	root: &Path,
+	options: &Options,
+	registrations: Vec<Registration>,
+	built: Option<&Ident>,
+) -> syn::Result<Vec<String>> {
+	let registrations: Vec<Registration> = registrations
+		.into_iter()
+		.filter(|registration| Some(&registration.enum_name) != built)
+		.collect();
+
+	if registrations.is_empty() {
+		return Ok(vec![]);
+	}
+
+	// Enums are built from any directory of the crate, so the whole crate is scanned for them.
+	let crate_root = env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| root.to_owned(), Into::into);
+	let crate_options = Options {
+		edition: options.edition.clone(),
+		scan_all: options.scan_all,
+		cache: options.cache.clone(),
+		..Options::default()
+	};
+	let enums = scan::built_enums(&crate_root, &crate_options)?;
+
+	Ok(registrations
+		.into_iter()
+		.filter(|registration| !enums.contains(&registration.enum_name))
+		.map(|registration| {
+			format!(
+				"{} at {} is registered as a variant of {}, which no enum_builder enum in the crate \
+				 builds",
+				registration.ident, registration.location, registration.enum_name
+			)
+		})
+		.collect())
+}
+
+/// Fails with one error per registration for an enum that nothing in the crate builds, other than
+/// `built`.
+pub(crate) fn check(
+	root: &Path,
+	options: &Options,
+	registrations: Vec<Registration>,
+	built: Option<&Ident>,
+) -> syn::Result<()> {
+	let mut error: Option<syn::Error> = None;
+
+	for message in unknown(root, options, registrations, built)? {
+		let unknown = syn::Error::new(Span::call_site(), message);
+
+		match &mut error {
+			Some(error) => error.combine(unknown),
+			None => error = Some(unknown),
+		}
+	}
+
+	error.map_or(Ok(()), Err)
+}
+
+/// Generates a deprecation warning per registration for an enum that nothing in the crate builds,
+/// other than `built`, as deprecation warnings are the only warnings generated code can trigger.
+pub(crate) fn warn(
+	root: &Path,
+	options: &Options,
+	registrations: Vec<Registration>,
+	built: &Ident,
+) -> syn::Result<TokenStream> {
+	let warnings = unknown(root, options, registrations, Some(built))?
+		.into_iter()
+		.map(|message| {
+			let note = format!(
+				"{}; use the strict_targets parameter to make this an error",
+				message
+			);
+
+			quote! {
+				const _: () = {
+					#[deprecated(note = #note)]
+					const fn unknown_target() {}
+
+					unknown_target();
+				};
+			}
+		});
+
+	Ok(quote!(#(#warnings)*))
+}
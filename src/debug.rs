@@ -0,0 +1,61 @@
+use std::{
+	env, fs,
+	io::Write,
+	path::PathBuf,
+	process::{Command, Stdio},
+};
+
+use proc_macro2::{Span, TokenStream};
+use syn::Ident;
+
+/// Formats `tokens` with rustfmt, falling back to their unformatted form when it's unavailable.
+pub(crate) fn format(tokens: &TokenStream) -> String {
+	let source = tokens.to_string();
+	let Ok(mut child) = Command::new(env::var("RUSTFMT").unwrap_or_else(|_| "rustfmt".to_owned()))
+		.args(["--edition", "2024", "--emit", "stdout"])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+	else {
+		return source;
+	};
+
+	if let Some(mut stdin) = child.stdin.take()
+		&& stdin.write_all(source.as_bytes()).is_err()
+	{
+		return source;
+	}
+
+	match child.wait_with_output() {
+		Ok(output) if output.status.success() => String::from_utf8(output.stdout).unwrap_or(source),
+		_ => source,
+	}
+}
+
+/// Writes the expansion of the enum named `ident` to `OUT_DIR/enum_builder/<ident>.rs` when the
+/// crate has a build script, or to stderr otherwise.
+pub(crate) fn dump(ident: &Ident, tokens: &TokenStream) -> syn::Result<()> {
+	let expansion = format(tokens);
+	let Some(out_dir) = env::var_os("OUT_DIR") else {
+		eprintln!("// enum_builder expansion of {}\n{}", ident, expansion);
+
+		return Ok(());
+	};
+
+	let dir = PathBuf::from(out_dir).join("enum_builder");
+	let path = dir.join(format!("{}.rs", ident));
+
+	fs::create_dir_all(&dir)
+		.and_then(|_| fs::write(&path, expansion))
+		.map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!(
+					"unable to write expansion to {}: {}",
+					path.to_string_lossy(),
+					err
+				),
+			)
+		})
+}
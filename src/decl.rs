@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use syn::{
+	Attribute, Generics, Ident, ItemEnum, Token, Visibility, braced,
+	meta::parser,
+	parse::{Parse, ParseStream, Parser},
+	punctuated::Punctuated,
+	token,
+};
+
+use crate::options::Options;
+
+/// The input of [macro@crate::enum_builder_decl]: an enum declaration, with an optional body,
+/// followed by the [macro@crate::enum_builder] parameters, each terminated by a semicolon.
+pub(crate) struct Decl {
+	pub item: ItemEnum,
+	pub options: Options,
+}
+
+impl Parse for Decl {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let vis: Visibility = input.parse()?;
+		let enum_token: Token![enum] = input.parse()?;
+		let ident: Ident = input.parse()?;
+		let mut generics: Generics = input.parse()?;
+
+		generics.where_clause = input.parse()?;
+
+		let (brace_token, variants) = if input.peek(token::Brace) {
+			let content;
+			let brace_token = braced!(content in input);
+
+			(brace_token, Punctuated::parse_terminated(&content)?)
+		} else {
+			input.parse::<Token![;]>()?;
+
+			(token::Brace::default(), Punctuated::new())
+		};
+
+		let mut options = Options::default();
+
+		while !input.is_empty() {
+			let mut param = TokenStream::new();
+
+			while !input.is_empty() && !input.peek(Token![;]) {
+				param.extend([input.parse::<proc_macro2::TokenTree>()?]);
+			}
+
+			input.parse::<Option<Token![;]>>()?;
+			parser(|meta| options.parse(meta)).parse2(param)?;
+		}
+
+		Ok(Self {
+			item: ItemEnum {
+				attrs,
+				vis,
+				enum_token,
+				ident,
+				generics,
+				brace_token,
+				variants,
+			},
+			options,
+		})
+	}
+}
@@ -0,0 +1,346 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+	Fields, FnArg, GenericParam, Generics, Ident, ItemEnum, ItemTrait, Pat, PatIdent, ReturnType,
+	Signature, TraitItem, Type, TypeParamBound, WherePredicate,
+};
+
+use crate::{case::to_snake_case, fields::matched_self, scan::module_qualifier};
+
+const ASYNC_TRAIT: &str = "async_trait";
+
+/// Combines the generic parameters of the enum with those of the dispatched trait.
+fn impl_generics(item: &ItemEnum, item_trait: &ItemTrait) -> Generics {
+	let mut generics = item.generics.clone();
+
+	generics
+		.params
+		.extend(item_trait.generics.params.iter().cloned());
+
+	let mut params: Vec<GenericParam> = generics.params.into_iter().collect();
+
+	params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+	generics.params = params.into_iter().collect();
+
+	if let Some(where_clause) = &item_trait.generics.where_clause {
+		generics
+			.make_where_clause()
+			.predicates
+			.extend(where_clause.predicates.iter().cloned());
+	}
+
+	generics
+}
+
+/// Copies `sig` with its typed arguments renamed, returning the names forwarded to the variants.
+fn forwarded_signature(
+	item_trait: &ItemTrait,
+	sig: &Signature,
+) -> syn::Result<(Signature, Vec<Ident>)> {
+	let mut sig = sig.clone();
+	let mut args = vec![];
+
+	if sig.receiver().is_none() {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"unable to dispatch {} because {} doesn't take self",
+				item_trait.ident, sig.ident
+			),
+		));
+	}
+
+	for (index, input) in sig.inputs.iter_mut().enumerate() {
+		if let FnArg::Typed(typed) = input {
+			let arg = format_ident!("arg{}", index);
+
+			typed.attrs.clear();
+			*typed.pat = Pat::Ident(PatIdent {
+				attrs: vec![],
+				by_ref: None,
+				mutability: None,
+				ident: arg.clone(),
+				subpat: None,
+			});
+			args.push(arg);
+		}
+	}
+
+	Ok((sig, args))
+}
+
+/// Path referring to `item_trait` from the enum's module, where `module` is the path of the module
+/// declaring the trait.
+pub(crate) fn trait_path(item_trait: &ItemTrait, module: &[String]) -> TokenStream {
+	let trait_ident = &item_trait.ident;
+	let qualifier = if module.is_empty() {
+		vec![]
+	} else {
+		module_qualifier(module)
+	};
+
+	quote!(#(#qualifier::)* #trait_ident)
+}
+
+/// Generates the impl of `item_trait` for the enum, forwarding every method to the payload of the
+/// current variant. `module` is the path of the module declaring the trait, and `dynamic` the
+/// variant boxing trait objects of it, if any.
+pub(crate) fn dispatch(
+	item: &ItemEnum,
+	item_trait: &ItemTrait,
+	module: &[String],
+	dynamic: Option<&Ident>,
+) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let trait_ident = &item_trait.ident;
+	let trait_path = trait_path(item_trait, module);
+	let generics = impl_generics(item, item_trait);
+	let (impl_generics, _, where_clause) = generics.split_for_impl();
+	let (_, ty_generics, _) = item.generics.split_for_impl();
+	let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+	let unsafety = &item_trait.unsafety;
+	// Traits rewritten by async_trait need their impls rewritten the same way.
+	let async_trait = item_trait.attrs.iter().find(|attr| {
+		attr.path()
+			.segments
+			.last()
+			.is_some_and(|segment| segment.ident == ASYNC_TRAIT)
+	});
+	let mut variants = vec![];
+
+	for variant in &item.variants {
+		match &variant.fields {
+			Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+				variants.push(&variant.ident);
+			}
+			_ => {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"unable to dispatch {} because variant {} doesn't hold a single payload",
+						trait_ident, variant.ident
+					),
+				));
+			}
+		}
+	}
+
+	let mut methods = vec![];
+
+	for trait_item in &item_trait.items {
+		let TraitItem::Fn(method) = trait_item else {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"unable to dispatch {} because it has associated items other than methods",
+					trait_ident
+				),
+			));
+		};
+
+		let (sig, args) = forwarded_signature(item_trait, &method.sig)?;
+		let method_ident = &sig.ident;
+		let forward = |inner: TokenStream| {
+			let call = quote!(#trait_path::#method_ident(#inner, #(#args),*));
+			let call = if sig.unsafety.is_some() {
+				quote!(unsafe { #call })
+			} else {
+				call
+			};
+
+			if sig.asyncness.is_some() {
+				quote!(#call.await)
+			} else {
+				call
+			}
+		};
+		let call = forward(quote!(inner));
+		let mut arms: Vec<TokenStream> = variants
+			.iter()
+			.filter(|variant| Some(**variant) != dynamic)
+			.map(|variant| quote!(Self::#variant(inner) => #call))
+			.collect();
+
+		if let Some(dynamic) = dynamic {
+			let inner = match sig.receiver() {
+				Some(receiver) if receiver.colon_token.is_none() => {
+					match (&receiver.reference, &receiver.mutability) {
+						(Some(_), Some(_)) => Some(quote!(&mut **inner)),
+						(Some(_), None) => Some(quote!(&**inner)),
+						_ => None,
+					}
+				}
+				_ => None,
+			};
+			let Some(inner) = inner else {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"unable to dispatch {} to the {} variant because {} doesn't take &self or &mut self",
+						trait_ident, dynamic, method_ident
+					),
+				));
+			};
+			let call = forward(inner);
+
+			arms.push(quote!(Self::#dynamic(inner) => #call));
+		}
+
+		let by_ref = sig
+			.receiver()
+			.is_some_and(|receiver| receiver.reference.is_some());
+		let matched = matched_self(by_ref && arms.is_empty());
+
+		methods.push(quote! {
+			#sig {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		});
+	}
+
+	Ok(quote! {
+		#async_trait
+		#unsafety impl #impl_generics #trait_path #trait_ty_generics for #ident #ty_generics #where_clause {
+			#(#methods)*
+		}
+	})
+}
+
+/// Whether `tokens` mention `Self` or an `impl Trait` type, which trait objects can't be used with.
+fn mentions_self(tokens: TokenStream) -> bool {
+	tokens.into_iter().any(|token| match token {
+		TokenTree::Ident(ident) => ident == "Self" || ident == "impl",
+		TokenTree::Group(group) => mentions_self(group.stream()),
+		_ => false,
+	})
+}
+
+/// Whether `item_trait` can be made into a trait object, as far as can be told from its
+/// declaration. Methods bounded by `Self: Sized` are excluded from trait objects, so they don't
+/// count.
+fn dyn_compatible(item_trait: &ItemTrait) -> bool {
+	let async_trait = item_trait.attrs.iter().any(|attr| {
+		attr.path()
+			.segments
+			.last()
+			.is_some_and(|segment| segment.ident == ASYNC_TRAIT)
+	});
+	let sized = |bound: &TypeParamBound| matches!(bound, TypeParamBound::Trait(bound) if bound.path.is_ident("Sized"));
+
+	if item_trait.supertraits.iter().any(sized) {
+		return false;
+	}
+
+	item_trait.items.iter().all(|trait_item| {
+		let TraitItem::Fn(method) = trait_item else {
+			return false;
+		};
+		let sig = &method.sig;
+		let excluded = sig.generics.where_clause.iter().any(|where_clause| {
+			where_clause.predicates.iter().any(|predicate| {
+				matches!(predicate, WherePredicate::Type(predicate)
+					if matches!(&predicate.bounded_ty, Type::Path(ty) if ty.path.is_ident("Self"))
+						&& predicate.bounds.iter().any(sized))
+			})
+		});
+		let by_reference = sig
+			.receiver()
+			.is_some_and(|receiver| receiver.reference.is_some() || receiver.colon_token.is_some());
+		let generic = sig
+			.generics
+			.params
+			.iter()
+			.any(|param| !matches!(param, GenericParam::Lifetime(_)));
+		let arguments = sig.inputs.iter().filter_map(|input| match input {
+			FnArg::Typed(typed) => Some(&*typed.ty),
+			FnArg::Receiver(_) => None,
+		});
+		let output = match &sig.output {
+			ReturnType::Type(_, ty) => Some(&**ty),
+			ReturnType::Default => None,
+		};
+
+		excluded
+			|| (by_reference
+				&& !generic && (sig.asyncness.is_none() || async_trait)
+				&& !arguments
+					.chain(output)
+					.any(|ty| mentions_self(ty.to_token_stream())))
+	})
+}
+
+/// Generates `as_dyn()` and `as_dyn_mut()`, or `as_dyn_<trait>()` and `as_dyn_<trait>_mut()` when
+/// `named`, borrowing the payload of the current variant as a trait object of `item_trait`, unless
+/// it can't be made into one. `module` is the path of the module declaring the trait, and `dynamic`
+/// the variant boxing trait objects of it, if any.
+pub(crate) fn as_dyn(
+	item: &ItemEnum,
+	item_trait: &ItemTrait,
+	module: &[String],
+	dynamic: Option<&Ident>,
+	named: bool,
+) -> TokenStream {
+	if !dyn_compatible(item_trait) {
+		return TokenStream::new();
+	}
+
+	let ident = &item.ident;
+	let trait_path = trait_path(item_trait, module);
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let (trait_generics, trait_ty_generics, trait_where_clause) =
+		item_trait.generics.split_for_impl();
+	let (as_dyn, as_dyn_mut) = if named {
+		let snake = to_snake_case(&item_trait.ident.to_string());
+
+		(
+			format_ident!("as_dyn_{}", snake),
+			format_ident!("as_dyn_{}_mut", snake),
+		)
+	} else {
+		(format_ident!("as_dyn"), format_ident!("as_dyn_mut"))
+	};
+	let doc = format!(
+		"Borrows the payload of this variant as a `dyn {}` trait object.",
+		item_trait.ident
+	);
+	let doc_mut = format!(
+		"Mutably borrows the payload of this variant as a `dyn {}` trait object.",
+		item_trait.ident
+	);
+	let variants: Vec<&Ident> = item
+		.variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.filter(|variant| Some(*variant) != dynamic)
+		.collect();
+	let dynamic = dynamic.map(|dynamic| {
+		(
+			quote!(Self::#dynamic(inner) => &**inner,),
+			quote!(Self::#dynamic(inner) => &mut **inner,),
+		)
+	});
+	let (dynamic, dynamic_mut) = dynamic.unzip();
+	let matched = matched_self(variants.is_empty() && dynamic.is_none());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#[doc = #doc]
+			pub fn #as_dyn #trait_generics (&self) -> &dyn #trait_path #trait_ty_generics #trait_where_clause {
+				match #matched {
+					#(Self::#variants(inner) => inner,)*
+					#dynamic
+				}
+			}
+
+			#[doc = #doc_mut]
+			pub fn #as_dyn_mut #trait_generics (&mut self) -> &mut dyn #trait_path #trait_ty_generics #trait_where_clause {
+				match #matched {
+					#(Self::#variants(inner) => inner,)*
+					#dynamic_mut
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,84 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use syn::{File, LitStr, parse_file};
+
+/// Editions the scanned sources can be written in.
+const EDITIONS: &[&str] = &["2015", "2018", "2021", "2024"];
+
+/// Keywords reserved from the 2018 edition on, which 2015 sources may use as plain identifiers.
+const KEYWORDS_2018: &[&str] = &["async", "await", "try"];
+
+/// Parses the value of the `edition` parameter, which must name a Rust edition.
+pub(crate) fn parse(lit: &LitStr) -> syn::Result<String> {
+	let edition = lit.value();
+
+	if !EDITIONS.contains(&edition.as_str()) {
+		return Err(syn::Error::new_spanned(
+			lit,
+			format!(
+				"unknown edition {}, expected one of {}",
+				edition,
+				EDITIONS.join(", ")
+			),
+		));
+	}
+
+	Ok(edition)
+}
+
+/// The `edition` declared in the `[package]` table of the manifest of the crate being compiled,
+/// if it can be read directly.
+fn manifest_edition() -> Option<String> {
+	let manifest = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR")?).join("Cargo.toml");
+	let manifest = fs::read_to_string(manifest).ok()?;
+	let mut package = false;
+
+	for line in manifest.lines().map(str::trim) {
+		if line.starts_with('[') {
+			package = line == "[package]";
+		} else if package
+			&& let Some((key, value)) = line.split_once('=')
+			&& key.trim() == "edition"
+		{
+			return Some(value.trim().trim_matches('"').to_owned());
+		}
+	}
+
+	None
+}
+
+/// The edition scanned sources are parsed with: the provided one, or the one of the crate being
+/// compiled.
+pub(crate) fn resolve(edition: Option<&str>) -> Option<String> {
+	edition.map(str::to_owned).or_else(manifest_edition)
+}
+
+/// Turns the identifiers of `tokens` that later editions reserve as keywords into raw identifiers.
+fn raw_keywords(tokens: TokenStream) -> TokenStream {
+	tokens
+		.into_iter()
+		.map(|tree| match tree {
+			TokenTree::Group(group) => {
+				let mut raw =
+					proc_macro2::Group::new(group.delimiter(), raw_keywords(group.stream()));
+
+				raw.set_span(group.span());
+				TokenTree::Group(raw)
+			}
+			TokenTree::Ident(ident) if KEYWORDS_2018.contains(&ident.to_string().as_str()) => {
+				TokenTree::Ident(Ident::new_raw(&ident.to_string(), ident.span()))
+			}
+			tree => tree,
+		})
+		.collect()
+}
+
+/// Parses the sources of a file written in `edition`, or in the latest edition if unknown.
+pub(crate) fn parse_source(src: &str, edition: Option<&str>) -> syn::Result<File> {
+	if edition != Some("2015") {
+		return parse_file(src);
+	}
+
+	syn::parse2(raw_keywords(src.parse()?))
+}
@@ -0,0 +1,63 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Fields, Ident, ItemEnum, Type, WherePredicate, parse_quote};
+
+use crate::fields::matched_self;
+
+/// Generates `Display` and `Error` for an enum aggregating error types, forwarding both to the
+/// payload of the current variant.
+pub(crate) fn error(item: &ItemEnum) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let mut names: Vec<&Ident> = vec![];
+	let mut payloads: Vec<&Type> = vec![];
+
+	for variant in &item.variants {
+		match &variant.fields {
+			Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+				names.push(&variant.ident);
+				payloads.push(&fields.unnamed[0].ty);
+			}
+			_ => {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"the error parameter requires every variant of {} to hold a single error, which {} doesn't",
+						ident, variant.ident
+					),
+				));
+			}
+		}
+	}
+
+	let mut generics = item.generics.clone();
+
+	// Generic payloads are only errors for some arguments.
+	if !generics.params.is_empty() {
+		let predicates = payloads
+			.iter()
+			.map(|payload| -> WherePredicate { parse_quote!(#payload: ::core::error::Error) });
+
+		generics.make_where_clause().predicates.extend(predicates);
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let matched = matched_self(names.is_empty());
+
+	Ok(quote! {
+		impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				match #matched {
+					#(Self::#names(inner) => ::core::fmt::Display::fmt(inner, f),)*
+				}
+			}
+		}
+
+		impl #impl_generics ::core::error::Error for #ident #ty_generics #where_clause {
+			fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+				match #matched {
+					#(Self::#names(inner) => ::core::error::Error::source(inner),)*
+				}
+			}
+		}
+	})
+}
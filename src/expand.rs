@@ -0,0 +1,1691 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+	Attribute, Fields, File, GenericParam, Generics, Ident, ImplItem, Item, ItemEnum, ItemTrait,
+	Meta, Visibility, parse::Parser, parse_quote, punctuated::Punctuated,
+};
+
+use crate::{
+	accessors, assertions, borrow,
+	case::{to_pascal_case, to_screaming_snake_case},
+	check, debug, dispatch, error, factory,
+	fields::matched_self,
+	filter, introspect, inventory, kind, lock, macros, manifest,
+	options::Options,
+	pregenerated, registry_file, scan, stats,
+	variant::Variant,
+	visitor,
+};
+
+const ENUM_DISPATCH: &str = "enum_dispatch";
+const DYNAMIC: &str = "Dynamic";
+
+/// Position of the enum_dispatch attribute on `item`, if any.
+fn enum_dispatch_index(item: &ItemEnum) -> Option<usize> {
+	item.attrs.iter().rposition(|attr| {
+		let path = match &attr.meta {
+			Meta::Path(path) => path,
+			Meta::List(list) => &list.path,
+			_ => return false,
+		};
+
+		path.is_ident(ENUM_DISPATCH)
+	})
+}
+
+/// Whether a `#[serde(...)]` attribute among `attrs` sets `key`.
+fn serde_sets(attrs: &[Attribute], key: &str) -> bool {
+	attrs.iter().any(|attr| match &attr.meta {
+		Meta::List(list) if list.path.is_ident("serde") => list
+			.tokens
+			.clone()
+			.into_iter()
+			.any(|token| matches!(token, TokenTree::Ident(ident) if ident == key)),
+		_ => false,
+	})
+}
+
+fn param_name(param: &GenericParam) -> String {
+	match param {
+		GenericParam::Lifetime(param) => param.lifetime.to_string(),
+		GenericParam::Type(param) => param.ident.to_string(),
+		GenericParam::Const(param) => param.ident.to_string(),
+	}
+}
+
+/// Replaces every occurrence of the identifier `from` in `tokens` with `to`.
+fn replace_ident(tokens: TokenStream, from: &Ident, to: &Ident) -> TokenStream {
+	tokens
+		.into_iter()
+		.map(|tree| match tree {
+			TokenTree::Ident(ident) if ident == *from => TokenTree::Ident(to.clone()),
+			TokenTree::Group(group) => {
+				let mut replaced = proc_macro2::Group::new(
+					group.delimiter(),
+					replace_ident(group.stream(), from, to),
+				);
+
+				replaced.set_span(group.span());
+				TokenTree::Group(replaced)
+			}
+			tree => tree,
+		})
+		.collect()
+}
+
+/// Prefixes the type and const parameters shared by several variant types with the name of their
+/// variant, e.g. `T` becomes `DogT` and `FishT`, so unrelated parameters don't get merged.
+/// Parameters declared on the enum itself are shared on purpose, and lifetimes are always shared.
+fn rename_generics(item: &ItemEnum, variants: &mut [Variant]) -> syn::Result<()> {
+	let declared: Vec<String> = item.generics.params.iter().map(param_name).collect();
+	let names: Vec<Vec<String>> = variants
+		.iter()
+		.map(|variant| {
+			variant
+				.generics
+				.params
+				.iter()
+				.filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+				.map(param_name)
+				.filter(|name| !declared.contains(name))
+				.collect()
+		})
+		.collect();
+
+	for (index, variant) in variants.iter_mut().enumerate() {
+		for name in &names[index] {
+			let shared = names
+				.iter()
+				.enumerate()
+				.any(|(other, names)| other != index && names.contains(name));
+
+			if !shared {
+				continue;
+			}
+
+			let from = format_ident!("{}", name);
+			let to = format_ident!("{}{}", variant.name, name);
+			let params = &variant.generics.params;
+			let where_clause = &variant.generics.where_clause;
+
+			variant.generics.params =
+				Punctuated::parse_terminated.parse2(replace_ident(quote!(#params), &from, &to))?;
+			variant.generics.where_clause =
+				syn::parse2(replace_ident(quote!(#where_clause), &from, &to))?;
+			variant.arguments = variant
+				.arguments
+				.take()
+				.map(|arguments| replace_ident(arguments, &from, &to));
+		}
+	}
+
+	Ok(())
+}
+
+/// Combines the generic parameters and where clauses declared on the enum with those of every
+/// variant type.
+fn merge_generics(options: &Options, item: &ItemEnum, variants: &[Variant]) -> Generics {
+	let mut params: Vec<GenericParam> = item.generics.params.iter().cloned().collect();
+
+	// Field-less variants don't reference the generics of the discovered types, and neither do
+	// variants storing another type.
+	let variants: Vec<&Variant> = variants
+		.iter()
+		.filter(|variant| !options.unit && variant.args.store.is_none())
+		.collect();
+
+	for variant in &variants {
+		for param in &variant.generics.params {
+			let name = param_name(param);
+
+			if params.iter().all(|existing| param_name(existing) != name) {
+				params.push(param.clone());
+			}
+		}
+	}
+
+	// Lifetimes must be declared before any type or const parameters.
+	params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+
+	let mut generics = item.generics.clone();
+	generics.params = params.into_iter().collect();
+
+	// The bounds of the variant types must hold wherever the enum is named.
+	for variant in &variants {
+		let Some(where_clause) = &variant.generics.where_clause else {
+			continue;
+		};
+
+		let predicates = &mut generics.make_where_clause().predicates;
+
+		for predicate in &where_clause.predicates {
+			let bound = predicate.to_token_stream().to_string();
+
+			if predicates
+				.iter()
+				.all(|existing| existing.to_token_stream().to_string() != bound)
+			{
+				predicates.push(predicate.clone());
+			}
+		}
+	}
+
+	generics
+}
+
+fn metadata(item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let meta_ident = format_ident!("{}VariantMeta", ident);
+	let table_ident = format_ident!("{}_METADATA", to_screaming_snake_case(&ident.to_string()));
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let count = item.variants.len();
+	let meta_doc = format!(
+		"Descriptive information attached to a variant of [`{}`].",
+		ident
+	);
+	let table_doc = format!(
+		"Metadata for every variant of [`{}`], in declaration order.",
+		ident
+	);
+	let entries = item.variants.iter().map(|enum_variant| {
+		let name = enum_variant.ident.to_string();
+		let fields = variants
+			.iter()
+			.find(|variant| variant.name == enum_variant.ident)
+			.into_iter()
+			.flat_map(|variant| &variant.args.meta)
+			.map(|(key, value)| quote!((#key, #value)));
+
+		quote! {
+			#meta_ident {
+				name: #name,
+				entries: &[#(#fields),*],
+			}
+		}
+	});
+	let arms = item.variants.iter().enumerate().map(|(index, variant)| {
+		let variant_ident = &variant.ident;
+
+		quote!(Self::#variant_ident { .. } => &#table_ident[#index])
+	});
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		#[doc = #meta_doc]
+		#[derive(::core::clone::Clone, ::core::marker::Copy, ::core::fmt::Debug)]
+		#vis struct #meta_ident {
+			/// The variant name.
+			pub name: &'static str,
+			/// Key/value pairs declared with `meta(...)` on the variant.
+			pub entries: &'static [(&'static str, &'static str)],
+		}
+
+		impl #meta_ident {
+			/// Returns the value declared for `key`, if any.
+			pub fn get(&self, key: &str) -> ::core::option::Option<&'static str> {
+				self.entries
+					.iter()
+					.find(|(entry, _)| *entry == key)
+					.map(|(_, value)| *value)
+			}
+		}
+
+		#[doc = #table_doc]
+		#vis static #table_ident: [#meta_ident; #count] = [#(#entries),*];
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the metadata declared for this variant.
+			pub fn metadata(&self) -> &'static #meta_ident {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+fn reexport(options: &Options, item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let vis = &item.vis;
+	let nesting = usize::from(options.mod_name.is_some());
+	let uses = variants
+		.iter()
+		.filter(|variant| variant.module.len() > nesting)
+		.map(|variant| {
+			let qualifier = variant.module_qualifier();
+			let ident = &variant.ident;
+			let shared = variants
+				.iter()
+				.filter(|other| other.ident == variant.ident)
+				.count() > 1;
+
+			// Types sharing a name are re-exported under their (disambiguated) variant names.
+			let rename = shared.then(|| {
+				let name = &variant.name;
+
+				quote!(as #name)
+			});
+
+			quote!(#vis use #(#qualifier::)* #ident #rename;)
+		});
+
+	quote!(#(#uses)*)
+}
+
+/// The visibility granting items declared in the module generated by the `mod_name` parameter the
+/// same access as `vis` grants items declared next to it.
+fn nested_vis(vis: &Visibility) -> Visibility {
+	match vis {
+		Visibility::Inherited => parse_quote!(pub(super)),
+		Visibility::Restricted(restricted) if restricted.path.is_ident("self") => {
+			parse_quote!(pub(super))
+		}
+		Visibility::Restricted(restricted) if restricted.path.is_ident("super") => {
+			parse_quote!(pub(in super::super))
+		}
+		_ => vis.clone(),
+	}
+}
+
+/// Allows the generated helper items in `tokens` to refer to deprecated variants, which would
+/// otherwise warn at the macro rather than where the variants are used.
+fn allow_deprecated(tokens: TokenStream) -> syn::Result<TokenStream> {
+	let mut file: File = syn::parse2(tokens)?;
+
+	for item in &mut file.items {
+		let attrs = match item {
+			Item::Impl(item) => &mut item.attrs,
+			Item::Fn(item) => &mut item.attrs,
+			Item::Const(item) => &mut item.attrs,
+			Item::Static(item) => &mut item.attrs,
+			_ => continue,
+		};
+
+		attrs.push(parse_quote!(#[allow(deprecated)]));
+	}
+
+	Ok(file.into_token_stream())
+}
+
+/// Adds `#[doc(hidden)]` to every generated item other than the enum named `ident`, and to the
+/// items of generated inherent impls, keeping them out of rustdoc while leaving them usable.
+fn hide_helpers(ident: &Ident, tokens: TokenStream) -> syn::Result<TokenStream> {
+	let mut file: File = syn::parse2(tokens)?;
+	let hidden: Attribute = parse_quote!(#[doc(hidden)]);
+
+	for item in &mut file.items {
+		let attrs = match item {
+			Item::Enum(item) if item.ident == *ident => continue,
+			Item::Impl(item) if item.trait_.is_none() => {
+				for item in &mut item.items {
+					match item {
+						ImplItem::Fn(item) => item.attrs.push(hidden.clone()),
+						ImplItem::Const(item) => item.attrs.push(hidden.clone()),
+						ImplItem::Type(item) => item.attrs.push(hidden.clone()),
+						_ => {}
+					}
+				}
+
+				continue;
+			}
+			Item::Struct(item) => &mut item.attrs,
+			Item::Enum(item) => &mut item.attrs,
+			Item::Union(item) => &mut item.attrs,
+			Item::Trait(item) => &mut item.attrs,
+			Item::Type(item) => &mut item.attrs,
+			Item::Fn(item) => &mut item.attrs,
+			Item::Const(item) => &mut item.attrs,
+			Item::Static(item) => &mut item.attrs,
+			Item::Macro(item) => &mut item.attrs,
+			Item::Use(item) => &mut item.attrs,
+			Item::Mod(item) => &mut item.attrs,
+			_ => continue,
+		};
+
+		attrs.push(hidden.clone());
+	}
+
+	Ok(file.into_token_stream())
+}
+
+/// Wraps the generated `tokens` into the module named by the `mod_name` parameter, re-exporting the
+/// enum with its declared visibility `vis`.
+fn wrap_module(
+	mod_name: &Ident,
+	vis: &Visibility,
+	ident: &Ident,
+	tokens: TokenStream,
+) -> TokenStream {
+	quote! {
+		#vis mod #mod_name {
+			#[allow(unused_imports)]
+			use super::*;
+
+			#tokens
+		}
+
+		#vis use #mod_name::#ident;
+	}
+}
+
+/// 64-bit FNV-1a hash, used for identifiers that must not change between builds.
+pub(crate) fn stable_hash(name: &str) -> u64 {
+	name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+		(hash ^ byte as u64).wrapping_mul(0x100000001b3)
+	})
+}
+
+fn stable_id(item: &ItemEnum) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let mut ids: Vec<(u64, &Ident)> = vec![];
+
+	for variant in &item.variants {
+		let id = stable_hash(&variant.ident.to_string());
+
+		if let Some((_, existing)) = ids.iter().find(|(existing, _)| *existing == id) {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"variants {} and {} have colliding stable ids",
+					existing, variant.ident
+				),
+			));
+		}
+
+		ids.push((id, &variant.ident));
+	}
+
+	let arms = ids
+		.iter()
+		.map(|(id, variant_ident)| quote!(Self::#variant_ident { .. } => #id));
+	let matched = matched_self(ids.is_empty());
+
+	Ok(quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns an identifier derived from the variant name, which stays the same as
+			/// variants are added or removed.
+			pub fn stable_id(&self) -> u64 {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	})
+}
+
+/// Generates `wire_id()`, returning the id each variant was registered with, and `wire_id()` and
+/// `from_wire_id()` on the kind enum if enabled. Every variant needs an id, and no two the same.
+fn wire_id(options: &Options, item: &ItemEnum, variants: &[Variant]) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let mut ids: Vec<(u32, &Variant)> = vec![];
+
+	for enum_variant in &item.variants {
+		let variant = variants
+			.iter()
+			.find(|variant| variant.name == enum_variant.ident);
+		let Some((variant, id)) =
+			variant.and_then(|variant| variant.args.id.map(|id| (variant, id)))
+		else {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"variant {} of {} has no wire id, which every variant needs once one is registered with the id parameter",
+					enum_variant.ident, ident
+				),
+			));
+		};
+
+		if let Some((_, existing)) = ids.iter().find(|(existing, _)| *existing == id) {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"variants {} at {} and {} at {} have the same wire id {}",
+					existing.name,
+					existing.location(),
+					variant.name,
+					variant.location(),
+					id
+				),
+			));
+		}
+
+		ids.push((id, variant));
+	}
+
+	let names: Vec<&Ident> = ids.iter().map(|(_, variant)| &variant.name).collect();
+	let ids: Vec<u32> = ids.iter().map(|(id, _)| *id).collect();
+	let mut tokens = quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the wire id this variant was registered with, which stays the same as
+			/// variants are renamed.
+			pub fn wire_id(&self) -> u32 {
+				match self {
+					#(Self::#names { .. } => #ids,)*
+				}
+			}
+		}
+	};
+
+	if options.kind {
+		let kind_ident = options.kind_ident(ident);
+
+		tokens.extend(quote! {
+			impl #kind_ident {
+				/// Returns the wire id this kind was registered with.
+				pub const fn wire_id(self) -> u32 {
+					match self {
+						#(Self::#names => #ids,)*
+					}
+				}
+
+				/// Returns the kind registered with the provided wire id, if any.
+				pub const fn from_wire_id(id: u32) -> ::core::option::Option<Self> {
+					match id {
+						#(#ids => ::core::option::Option::Some(Self::#names),)*
+						_ => ::core::option::Option::None,
+					}
+				}
+			}
+		});
+	}
+
+	Ok(tokens)
+}
+
+fn default(options: &Options, item: &ItemEnum, variants: &[Variant]) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let defaults: Vec<&Variant> = variants
+		.iter()
+		.filter(|variant| variant.args.default)
+		.collect();
+
+	let variant = match defaults.as_slice() {
+		[variant] => variant,
+		[] => {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"no variant of {} is registered with the default parameter",
+					ident
+				),
+			));
+		}
+		[..] => {
+			let names: Vec<String> = defaults
+				.iter()
+				.map(|variant| variant.name.to_string())
+				.collect();
+
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"only one variant of {} can be registered with the default parameter, found {}",
+					ident,
+					names.join(", ")
+				),
+			));
+		}
+	};
+
+	let variant_ident = &variant.name;
+	let value = if options.unit {
+		quote!(Self::#variant_ident)
+	} else {
+		quote!(Self::#variant_ident(::core::default::Default::default()))
+	};
+
+	Ok(quote! {
+		impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+			fn default() -> Self {
+				#value
+			}
+		}
+	})
+}
+
+/// Forms the variant identifier for a discovered type, applying any configured prefix/suffix stripping.
+fn variant_name(options: &Options, ident: &Ident) -> Ident {
+	let name = ident.to_string();
+	let mut stripped = name.as_str();
+
+	if let Some(prefix) = &options.strip_prefix {
+		stripped = stripped.strip_prefix(prefix.as_str()).unwrap_or(stripped);
+	}
+
+	if let Some(suffix) = &options.strip_suffix {
+		stripped = stripped.strip_suffix(suffix.as_str()).unwrap_or(stripped);
+	}
+
+	// Keep the type name if stripping wouldn't leave a valid identifier behind.
+	if !stripped.starts_with(|c: char| c.is_alphabetic() || c == '_') || stripped == "_" {
+		return ident.clone();
+	}
+
+	Ident::new(stripped, ident.span())
+}
+
+fn find_duplicate(variants: &[Variant]) -> Option<(&Variant, &Variant)> {
+	variants.iter().enumerate().find_map(|(index, variant)| {
+		variants[..index]
+			.iter()
+			.find(|existing| existing.name == variant.name)
+			.map(|existing| (existing, variant))
+	})
+}
+
+/// Rejects variants sharing a name, or prefixes them with their module names when `disambiguate`
+/// is set.
+fn resolve_duplicates(options: &Options, variants: &mut [Variant]) -> syn::Result<()> {
+	if options.disambiguate {
+		let colliding: Vec<bool> = variants
+			.iter()
+			.map(|variant| {
+				variants
+					.iter()
+					.filter(|other| other.name == variant.name)
+					.count() > 1
+			})
+			.collect();
+
+		for (variant, colliding) in variants.iter_mut().zip(colliding) {
+			if let Some(module) = variant.module_name().filter(|_| colliding) {
+				variant.name = format_ident!("{}{}", to_pascal_case(module), variant.name);
+
+				// The bare type name is ambiguous, so refer to it through its module instead.
+				variant.qualifier = variant.module_qualifier();
+			}
+		}
+	}
+
+	let Some((first, second)) = find_duplicate(variants) else {
+		return Ok(());
+	};
+
+	let hint = if options.disambiguate {
+		""
+	} else {
+		"; use the disambiguate parameter to prefix colliding variants with their module name"
+	};
+
+	Err(syn::Error::new(
+		Span::call_site(),
+		format!(
+			"duplicate variant {} discovered at {} and {}{}",
+			second.name,
+			first.location(),
+			second.location(),
+			hint
+		),
+	))
+}
+
+/// Generates `impl From<Base> for Enum`, mapping every variant of the extended enum onto the
+/// variant of the same name.
+fn extends(item: &ItemEnum, base: &ItemEnum) -> TokenStream {
+	let ident = &item.ident;
+	let base_ident = &base.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let (_, base_ty_generics, _) = base.generics.split_for_impl();
+	let arms = base.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+
+		match &variant.fields {
+			Fields::Unit => quote!(#base_ident::#variant_ident => Self::#variant_ident),
+			Fields::Unnamed(fields) => {
+				let bindings: Vec<Ident> = (0..fields.unnamed.len())
+					.map(|index| format_ident!("field{}", index))
+					.collect();
+
+				quote!(#base_ident::#variant_ident(#(#bindings),*) => Self::#variant_ident(#(#bindings),*))
+			}
+			Fields::Named(fields) => {
+				let bindings = fields.named.iter().map(|field| &field.ident);
+				let values = bindings.clone();
+
+				quote!(#base_ident::#variant_ident { #(#bindings),* } => Self::#variant_ident { #(#values),* })
+			}
+		}
+	});
+
+	quote! {
+		impl #impl_generics ::core::convert::From<#base_ident #base_ty_generics> for #ident #ty_generics #where_clause {
+			fn from(value: #base_ident #base_ty_generics) -> Self {
+				match value {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `impl From<Enum> for Source` and `impl TryFrom<Source> for Enum` for an enum built
+/// from the variants of `source`, handing back values of other variants as the error.
+fn subset(
+	options: &Options,
+	item: &ItemEnum,
+	source: &ItemEnum,
+	variants: &[Variant],
+) -> TokenStream {
+	let ident = &item.ident;
+	let source_ident = &source.ident;
+	let (impl_generics, source_ty_generics, where_clause) = source.generics.split_for_impl();
+	let (_, ty_generics, _) = item.generics.split_for_impl();
+	let names: Vec<&Ident> = variants.iter().map(|variant| &variant.name).collect();
+	let fields = (!options.unit).then(|| quote!((value)));
+	let fields = vec![fields; names.len()];
+
+	quote! {
+		impl #impl_generics ::core::convert::From<#ident #ty_generics> for #source_ident #source_ty_generics #where_clause {
+			fn from(value: #ident #ty_generics) -> Self {
+				match value {
+					#(#ident::#names #fields => Self::#names #fields,)*
+				}
+			}
+		}
+
+		impl #impl_generics ::core::convert::TryFrom<#source_ident #source_ty_generics> for #ident #ty_generics #where_clause {
+			type Error = #source_ident #source_ty_generics;
+
+			fn try_from(value: #source_ident #source_ty_generics) -> ::core::result::Result<Self, Self::Error> {
+				match value {
+					#(#source_ident::#names #fields => ::core::result::Result::Ok(Self::#names #fields),)*
+					#[allow(unreachable_patterns)]
+					value => ::core::result::Result::Err(value),
+				}
+			}
+		}
+	}
+}
+
+/// An enum assembled from its declaration and the variants discovered for it.
+struct Assembled {
+	item: ItemEnum,
+	variants: Vec<Variant>,
+	/// The assembled enum named by the `extends` parameter.
+	base: Option<ItemEnum>,
+	/// The assembled enum named by the `from = [str]` parameter.
+	source: Option<ItemEnum>,
+	/// Inner enums holding the variants discovered in each child module, with `group_by_module`.
+	groups: Vec<Group>,
+	/// Registrations for other enums found while discovering the variants.
+	registrations: Vec<scan::Registration>,
+}
+
+/// An inner enum generated by the `group_by_module` parameter.
+struct Group {
+	item: ItemEnum,
+	variants: Vec<Variant>,
+	/// The variant of the outer enum holding the inner enum.
+	name: Ident,
+}
+
+/// Moves the variants declared in child modules of the enum's module into one inner enum per
+/// module.
+fn group_by_module(options: &Options, item: &ItemEnum, variants: &mut Vec<Variant>) -> Vec<Group> {
+	let mut groups: Vec<Group> = vec![];
+
+	// Variants of an enum declared in the module generated by mod_name are relative to it.
+	let nesting = usize::from(options.mod_name.is_some());
+
+	for variant in std::mem::take(variants) {
+		let Some(module) = variant
+			.module
+			.get(nesting)
+			.filter(|segment| *segment != "super")
+		else {
+			variants.push(variant);
+			continue;
+		};
+
+		let name = format_ident!("{}", to_pascal_case(module));
+
+		let group = match groups.iter_mut().find(|group| group.name == name) {
+			Some(group) => group,
+			None => {
+				let vis = &item.vis;
+				let group_ident = format_ident!("{}{}", name, item.ident);
+				let doc = format!(
+					"Variants of [`{}`] declared in the `{}` module.",
+					item.ident, module
+				);
+				let derive = (!options.derive.is_empty()).then(|| {
+					let paths = &options.derive;
+
+					quote!(#[derive(#(#paths),*)])
+				});
+
+				groups.push(Group {
+					item: parse_quote! {
+						#[doc = #doc]
+						#derive
+						#vis enum #group_ident {}
+					},
+					variants: vec![],
+					name,
+				});
+				groups.last_mut().unwrap()
+			}
+		};
+
+		group.variants.push(variant);
+	}
+
+	for group in &mut groups {
+		group.item.generics = merge_generics(options, &group.item, &group.variants);
+
+		for variant in &group.variants {
+			let variant_ident = &variant.name;
+			let payload = variant.payload();
+			let fields = (!options.unit).then(|| quote!((#payload)));
+			let deprecated = variant.deprecated();
+
+			group
+				.item
+				.variants
+				.push(parse_quote!(#deprecated #variant_ident #fields));
+		}
+	}
+
+	groups
+}
+
+/// Generates conversions from the inner enums into the outer enum, and from each grouped payload
+/// into both.
+fn group_conversions(options: &Options, item: &ItemEnum, groups: &[Group]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let mut tokens = TokenStream::new();
+
+	for group in groups {
+		let name = &group.name;
+		let group_ident = &group.item.ident;
+		let (group_impl_generics, group_ty_generics, group_where_clause) =
+			group.item.generics.split_for_impl();
+
+		tokens.extend(quote! {
+			impl #impl_generics ::core::convert::From<#group_ident #group_ty_generics> for #ident #ty_generics #where_clause {
+				fn from(value: #group_ident #group_ty_generics) -> Self {
+					Self::#name(value)
+				}
+			}
+		});
+
+		if options.unit {
+			continue;
+		}
+
+		for variant in &group.variants {
+			let variant_ident = &variant.name;
+			let payload = variant.payload();
+
+			tokens.extend(quote! {
+				impl #group_impl_generics ::core::convert::From<#payload> for #group_ident #group_ty_generics #group_where_clause {
+					fn from(value: #payload) -> Self {
+						Self::#variant_ident(value)
+					}
+				}
+
+				impl #impl_generics ::core::convert::From<#payload> for #ident #ty_generics #where_clause {
+					fn from(value: #payload) -> Self {
+						Self::#name(#group_ident::#variant_ident(value))
+					}
+				}
+			});
+		}
+	}
+
+	tokens
+}
+
+/// Assembles the enum named by the `extends` parameter, rebasing its discovered variants onto the
+/// module of the extending enum declared in `file`.
+fn assemble_base(
+	options: &Options,
+	base: &Ident,
+	root: &Path,
+	file: &Path,
+	extending: &[String],
+) -> syn::Result<(Options, Assembled)> {
+	if extending.contains(&base.to_string()) {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"{} is built from itself through {}",
+				base,
+				extending.join(" -> ")
+			),
+		));
+	}
+
+	let Some((base_file, base_item, base_options)) = scan::find_enum(base, root, options)? else {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"unable to find an enum_builder enum named {} under {}",
+				base,
+				scan::display_path(root)
+			),
+		));
+	};
+
+	if base_options.unit != options.unit {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the variants of {} can only be included in enums that also use the unit parameter",
+				base
+			),
+		));
+	}
+
+	let mut assembled = assemble(&base_options, base_item, &base_file, extending)?;
+
+	for variant in &mut assembled.variants {
+		variant.module = scan::module_path(file, &variant.file);
+
+		if options.qualified_paths || !variant.qualifier.is_empty() {
+			variant.qualifier = variant.module_qualifier();
+		}
+	}
+
+	Ok((base_options, assembled))
+}
+
+/// Finds the trait named `trait_ident` among the sources scanned for the enum declared in `file`,
+/// along with the path of its module.
+fn find_trait(
+	options: &Options,
+	file: &Path,
+	trait_ident: &Ident,
+) -> syn::Result<(ItemTrait, Vec<String>)> {
+	let root = scan_root(options, file)?;
+	let (item_trait, mut module) = scan::find_trait(trait_ident, &root, file, options)?
+		.ok_or_else(|| {
+			syn::Error::new(
+				Span::call_site(),
+				format!(
+					"unable to find a trait named {} under {}",
+					trait_ident,
+					scan::display_path(&root)
+				),
+			)
+		})?;
+
+	if options.mod_name.is_some() {
+		module.insert(0, "super".to_owned());
+	}
+
+	Ok((item_trait, module))
+}
+
+/// The file declaring the enum named `ident`, which is `local_file`, the file of the macro call
+/// site, when the compiler exposes it. Otherwise, like in some doc tests or build systems, it's
+/// found by scanning the crate under `CARGO_MANIFEST_DIR` for the enum's declaration.
+pub(crate) fn call_site_file(
+	options: &Options,
+	ident: &Ident,
+	local_file: Option<PathBuf>,
+) -> syn::Result<PathBuf> {
+	if let Some(file) = local_file {
+		return Ok(file);
+	}
+
+	// The files parameter is relative to the unknown call site, so the whole crate is scanned.
+	let crate_options = Options {
+		edition: options.edition.clone(),
+		..Options::default()
+	};
+
+	if let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR")
+		&& let Some((file, _, _)) = scan::find_enum(ident, Path::new(&dir), &crate_options)?
+	{
+		return Ok(file);
+	}
+
+	// Variants of sources included through path_env have no module, so the declaring file is only
+	// needed to name the scanned directory, which path_env already does.
+	if let Some(var) = &options.path_env
+		&& let Some(dir) = std::env::var_os(var)
+	{
+		return Ok(PathBuf::from(dir).join("lib.rs"));
+	}
+
+	Err(syn::Error::new(
+		Span::call_site(),
+		format!(
+			"unable to determine the file declaring {}, as the compiler doesn't expose it and it \
+			 isn't declared in a source file under CARGO_MANIFEST_DIR; use the path_env and path \
+			 parameters to name the directory to scan",
+			ident
+		),
+	))
+}
+
+/// The directory scanned for the variants of the enum declared in `file`.
+fn scan_root(options: &Options, file: &Path) -> syn::Result<PathBuf> {
+	let dir = match &options.path_env {
+		Some(var) => PathBuf::from(std::env::var_os(var).ok_or_else(|| {
+			syn::Error::new(
+				Span::call_site(),
+				format!(
+					"the environment variable {} named by path_env isn't set",
+					var
+				),
+			)
+		})?),
+		None => file.parent().unwrap().to_owned(),
+	};
+
+	Ok(match &options.path {
+		Some(path) => dir.join(path),
+		None => dir,
+	})
+}
+
+/// Generates constants including every file the variants were discovered in, so the enum is
+/// rebuilt when they change.
+fn track_files(variants: &[Variant]) -> TokenStream {
+	let mut files: Vec<String> = vec![];
+
+	for variant in variants {
+		let file = variant.file.to_string_lossy().into_owned();
+
+		if !files.contains(&file) {
+			files.push(file);
+		}
+	}
+
+	quote!(#(const _: &[u8] = ::core::include_bytes!(#files);)*)
+}
+
+/// Builds the final enum item from its declaration in `file` and the variants discovered for it.
+/// `extending` lists the enums currently being assembled through `extends` parameters.
+fn assemble(
+	options: &Options,
+	mut item: ItemEnum,
+	file: &Path,
+	extending: &[String],
+) -> syn::Result<Assembled> {
+	let root = scan_root(options, file)?;
+	// Enums built from another enum's variants don't discover any of their own.
+	let (mut variants, registrations) = match &options.source {
+		Some(_) => (vec![], vec![]),
+		None => scan::discover(&item.ident, &root, file, options)?,
+	};
+
+	if let Some(path) = &options.registry_file {
+		variants.extend(registry_file::load(&item.ident, path)?);
+	}
+
+	filter::retain(options, &mut variants);
+
+	// Generated sources are included into the enum's module rather than declared as modules.
+	if options.path_env.is_some() {
+		for variant in &mut variants {
+			variant.module.clear();
+		}
+	}
+
+	for variant in &mut variants {
+		variant.name = match &variant.args.name {
+			Some(name) => name.clone(),
+			None => variant_name(options, &variant.ident),
+		};
+
+		if options.qualified_paths {
+			variant.qualifier = variant.module_qualifier();
+		}
+	}
+
+	let mut base = None;
+
+	if let Some(base_ident) = &options.extends {
+		let extending = [extending, &[item.ident.to_string()]].concat();
+		let (_, assembled) = assemble_base(options, base_ident, &root, file, &extending)?;
+
+		// Variants the base enum didn't discover are carried over as declared variants.
+		for variant in &assembled.item.variants {
+			if assembled
+				.variants
+				.iter()
+				.all(|discovered| discovered.name != variant.ident)
+			{
+				item.variants.push(variant.clone());
+			}
+		}
+
+		variants.splice(0..0, assembled.variants);
+		base = Some(assembled.item);
+	}
+
+	let mut source = None;
+
+	if let Some(source_ident) = &options.source {
+		if let Some(variant) = item.variants.first() {
+			return Err(syn::Error::new_spanned(
+				variant,
+				format!(
+					"{} is built from the variants of {} and can't declare variants of its own",
+					item.ident, source_ident
+				),
+			));
+		}
+
+		let extending = [extending, &[item.ident.to_string()]].concat();
+		let (_, assembled) = assemble_base(options, source_ident, &root, file, &extending)?;
+
+		variants = assembled
+			.variants
+			.into_iter()
+			.filter(|variant| {
+				options
+					.tag
+					.as_ref()
+					.is_none_or(|tag| variant.args.tags.contains(tag))
+			})
+			.collect();
+		source = Some(assembled.item);
+	}
+
+	// The enum is declared in the module generated by mod_name, a child of the module it's written
+	// in, which the variants are now relative to.
+	if options.mod_name.is_some() && extending.is_empty() {
+		for variant in &mut variants {
+			variant.module.insert(0, "super".to_owned());
+
+			if options.qualified_paths || !variant.qualifier.is_empty() {
+				variant.qualifier = variant.module_qualifier();
+			}
+		}
+	}
+
+	resolve_duplicates(options, &mut variants)?;
+
+	if let Some(vis) = &options.vis {
+		item.vis = vis.clone();
+	}
+
+	if options.mod_name.is_some() && extending.is_empty() {
+		item.vis = nested_vis(&item.vis);
+	}
+
+	if let Some(variant) = variants.iter().find(|variant| {
+		item.variants
+			.iter()
+			.any(|declared| declared.ident == variant.name)
+	}) {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"variant {} discovered at {} is already declared on {}",
+				variant.name,
+				variant.location(),
+				item.ident
+			),
+		));
+	}
+
+	if options.repr.is_none()
+		&& !options.unit
+		&& let Some(variant) = variants
+			.iter()
+			.find(|variant| variant.args.discriminant.is_some())
+	{
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"variant {} has an explicit discriminant, which requires either the repr or unit parameter",
+				variant.name
+			),
+		));
+	}
+
+	if let Some(repr) = &options.repr {
+		item.attrs.push(parse_quote!(#[repr(#repr)]));
+	}
+
+	if options.non_exhaustive {
+		item.attrs.push(parse_quote!(#[non_exhaustive]));
+	}
+
+	if !options.derive.is_empty() {
+		let paths = &options.derive;
+
+		item.attrs.push(parse_quote!(#[derive(#(#paths),*)]));
+	}
+
+	if !options.strum.is_empty() {
+		let paths = &options.strum;
+
+		item.attrs
+			.push(parse_quote!(#[derive(#(::strum::#paths),*)]));
+	}
+
+	// The schema derive reads the serde attributes, so it's attached before them.
+	if options.schemars {
+		item.attrs
+			.push(parse_quote!(#[derive(::schemars::JsonSchema)]));
+	}
+
+	if let Some(serde) = &options.serde {
+		item.attrs
+			.push(parse_quote!(#[derive(::serde::Serialize, ::serde::Deserialize)]));
+		item.attrs.push(parse_quote!(#[serde(#serde)]));
+
+		// Variants are renamed one by one, so serde splits their names into words like the
+		// generated code, which conflicts with a rename_all serde attribute.
+		if options.rename_all.is_some() && serde_sets(&item.attrs, "rename_all") {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				"the rename_all parameter already renames the variants for serde, so it can't be \
+				 combined with a rename_all serde attribute",
+			));
+		}
+	}
+
+	if !options.unit {
+		rename_generics(&item, &mut variants)?;
+	}
+
+	item.generics = merge_generics(options, &item, &variants);
+
+	let groups = if options.group_by_module {
+		group_by_module(options, &item, &mut variants)
+	} else {
+		vec![]
+	};
+
+	let mut generated: Vec<(Ident, &str)> = groups
+		.iter()
+		.map(|group| (group.name.clone(), "group_by_module"))
+		.collect();
+
+	if options.dyn_fallback.is_some() {
+		generated.push((format_ident!("{}", DYNAMIC), "dyn_fallback"));
+	}
+
+	if let Some(other) = &options.other {
+		generated.push((other.ident.clone(), "other"));
+	}
+
+	for (name, parameter) in &generated {
+		if let Some(variant) = variants.iter().find(|variant| variant.name == *name) {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"variant {} discovered at {} collides with the variant generated by the {} parameter",
+					variant.name,
+					variant.location(),
+					parameter
+				),
+			));
+		}
+	}
+
+	for variant in &variants {
+		let variant_ident = &variant.name;
+		let payload = variant.payload();
+		let fields = (!options.unit).then(|| quote!((#payload)));
+		let discriminant = variant
+			.args
+			.discriminant
+			.as_ref()
+			.map(|discriminant| quote!(= #discriminant));
+		let strum = variant
+			.args
+			.strum
+			.as_ref()
+			.map(|strum| quote!(#[strum(#strum)]));
+		let deprecated = variant.deprecated();
+
+		item.variants
+			.push(parse_quote!(#deprecated #strum #variant_ident #fields #discriminant));
+	}
+
+	for group in &groups {
+		let name = &group.name;
+		let group_ident = &group.item.ident;
+		let (_, group_ty_generics, _) = group.item.generics.split_for_impl();
+
+		item.variants
+			.push(parse_quote!(#name(#group_ident #group_ty_generics)));
+	}
+
+	if let Some(trait_ident) = &options.dyn_fallback {
+		let (item_trait, module) = find_trait(options, file, trait_ident)?;
+		let trait_path = dispatch::trait_path(&item_trait, &module);
+		let dynamic = format_ident!("{}", DYNAMIC);
+		let box_path = options.box_path();
+
+		item.variants
+			.push(parse_quote!(#dynamic(#box_path<dyn #trait_path>)));
+	}
+
+	if let Some(other) = &options.other {
+		item.variants.push(other.clone());
+	}
+
+	if options.serde.is_some() && options.rename_all.is_some() {
+		for variant in &mut item.variants {
+			if !serde_sets(&variant.attrs, "rename") {
+				let name = options.external_name(&variant.ident);
+
+				variant.attrs.push(parse_quote!(#[serde(rename = #name)]));
+			}
+		}
+	}
+
+	Ok(Assembled {
+		item,
+		variants,
+		base,
+		source,
+		groups,
+		registrations,
+	})
+}
+
+pub(crate) fn enum_builder(
+	options: Options,
+	item: ItemEnum,
+	file: &Path,
+) -> syn::Result<TokenStream> {
+	options.validate()?;
+	// Counters left over from an expansion that failed are discarded.
+	stats::take();
+
+	if (options.from || options.error) && enum_dispatch_index(&item).is_some() {
+		let parameter = if options.from { "from" } else { "error" };
+
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the {} parameter can't be combined with enum_dispatch, which already implements From for every variant",
+				parameter
+			),
+		));
+	}
+
+	let vis = options.vis.clone().unwrap_or_else(|| item.vis.clone());
+	let Assembled {
+		item: assembled,
+		variants,
+		base,
+		source,
+		groups,
+		registrations,
+	} = assemble(&options, item.clone(), file, &[])?;
+
+	if let Some(expected) = options.expect_count {
+		let discovered: Vec<String> = variants
+			.iter()
+			.chain(groups.iter().flat_map(|group| &group.variants))
+			.map(|variant| format!("{} ({})", variant.name, variant.location()))
+			.collect();
+
+		if discovered.len() != expected {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"expected {} variants of {} to be discovered, found {}: {}",
+					expected,
+					item.ident,
+					discovered.len(),
+					if discovered.is_empty() {
+						"none".to_owned()
+					} else {
+						discovered.join(", ")
+					}
+				),
+			));
+		}
+	}
+
+	if assembled.variants.is_empty() && !options.allow_empty {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"no variants found for {} under {}; use the allow_empty parameter to build an empty enum",
+				item.ident,
+				scan::display_path(&scan_root(&options, file)?)
+			),
+		));
+	}
+
+	let mut item = assembled;
+
+	// enum_dispatch can't dispatch to an enum without variants.
+	if item.variants.is_empty()
+		&& let Some(index) = enum_dispatch_index(&item)
+	{
+		item.attrs.remove(index);
+	}
+
+	let mut tokens = item.to_token_stream();
+
+	for group in &groups {
+		group.item.to_tokens(&mut tokens);
+	}
+
+	if !groups.is_empty() {
+		tokens.extend(group_conversions(&options, &item, &groups));
+	}
+
+	if let Some(base) = &base {
+		tokens.extend(extends(&item, base));
+	}
+
+	if let Some(source) = &source {
+		tokens.extend(subset(&options, &item, source, &variants));
+	}
+
+	if options.reexport {
+		tokens.extend(reexport(&options, &item, &variants));
+	}
+
+	if options.default || variants.iter().any(|variant| variant.args.default) {
+		tokens.extend(default(&options, &item, &variants)?);
+	}
+
+	if options.stable_id {
+		tokens.extend(stable_id(&item)?);
+	}
+
+	if variants.iter().any(|variant| variant.args.id.is_some()) {
+		tokens.extend(wire_id(&options, &item, &variants)?);
+	}
+
+	for trait_ident in &options.dispatch {
+		let (item_trait, module) = find_trait(&options, file, trait_ident)?;
+		let dynamic = (options.dyn_fallback.as_ref() == Some(trait_ident))
+			.then(|| format_ident!("{}", DYNAMIC));
+
+		tokens.extend(dispatch::dispatch(
+			&item,
+			&item_trait,
+			&module,
+			dynamic.as_ref(),
+		)?);
+		tokens.extend(dispatch::as_dyn(
+			&item,
+			&item_trait,
+			&module,
+			dynamic.as_ref(),
+			options.dispatch.len() > 1,
+		));
+	}
+
+	if options.kind {
+		tokens.extend(kind::kind(&options, &item));
+	}
+
+	if options.kind
+		&& variants
+			.iter()
+			.any(|variant| variant.args.priority.is_some())
+	{
+		tokens.extend(kind::by_priority(&options, &item, &variants));
+	}
+
+	if options.same_kind {
+		tokens.extend(kind::same_kind(&item));
+	}
+
+	if options.from_str {
+		tokens.extend(kind::from_str(&options, &item));
+	}
+
+	if options.accessors {
+		tokens.extend(accessors::accessors(&item));
+	}
+
+	if options.from || options.error {
+		tokens.extend(accessors::from(&item, &variants));
+	}
+
+	if options.try_from {
+		tokens.extend(accessors::try_from(&item, &variants));
+	}
+
+	// Generated macros name the enum's module from the crate root, as they can be invoked anywhere.
+	let module = scan::crate_module_path(file).map(|mut module| {
+		module.extend(options.mod_name.iter().map(ToString::to_string));
+		module
+	});
+
+	if options.for_each_variant {
+		tokens.extend(macros::for_each_variant(
+			&item,
+			&variants,
+			module.as_deref(),
+		));
+	}
+
+	if let Some(hook) = &options.variant_macro {
+		for group in &groups {
+			tokens.extend(macros::variant_hook(hook, &group.item, &group.variants));
+		}
+
+		tokens.extend(macros::variant_hook(hook, &item, &variants));
+	}
+
+	if options.match_variant {
+		tokens.extend(macros::match_variant(&item, module.as_deref()));
+	}
+
+	if options.assert_variants {
+		let names: Vec<&Ident> = variants
+			.iter()
+			.chain(groups.iter().flat_map(|group| &group.variants))
+			.map(|variant| &variant.name)
+			.collect();
+
+		tokens.extend(macros::assert_variants(&item, &names));
+	}
+
+	if options.visitor {
+		tokens.extend(visitor::visitor(&item));
+	}
+
+	if options.as_ref {
+		tokens.extend(borrow::as_ref(&item));
+	}
+
+	if options.as_mut {
+		tokens.extend(borrow::as_mut(&item));
+	}
+
+	if options.all_default {
+		tokens.extend(factory::all_default(&item));
+	}
+
+	if options.arbitrary {
+		tokens.extend(factory::arbitrary(&item));
+	}
+
+	if options.proptest {
+		tokens.extend(factory::proptest(&item)?);
+	}
+
+	if options.registry {
+		tokens.extend(factory::registry(&options, &item, &variants)?);
+	}
+
+	if options.from_name {
+		tokens.extend(factory::from_name(&options, &item));
+	}
+
+	if options.serde_factory {
+		tokens.extend(factory::from_tagged_value(&options, &item, &variants));
+	}
+
+	if options.display {
+		tokens.extend(introspect::display(&options, &item));
+	}
+
+	if options.error {
+		tokens.extend(error::error(&item)?);
+	}
+
+	if options.names {
+		tokens.extend(introspect::names(&options, &item));
+	}
+
+	if options.index {
+		tokens.extend(introspect::index(&options, &item));
+	}
+
+	let group_names: Vec<Ident> = groups.iter().map(|group| group.name.clone()).collect();
+
+	if options.module_path {
+		for group in &groups {
+			tokens.extend(introspect::module_path(&group.item, &group.variants, &[]));
+		}
+
+		tokens.extend(introspect::module_path(&item, &variants, &group_names));
+	}
+
+	if options.verbose_debug {
+		for group in &groups {
+			tokens.extend(introspect::debug(&group.item, &group.variants, &[]));
+		}
+
+		tokens.extend(introspect::debug(&item, &variants, &group_names));
+	}
+
+	if options.source_location {
+		for group in &groups {
+			tokens.extend(introspect::source_location(
+				&group.item,
+				&group.variants,
+				&[],
+				file,
+			));
+		}
+
+		tokens.extend(introspect::source_location(
+			&item,
+			&variants,
+			&group_names,
+			file,
+		));
+	}
+
+	if options.type_ids {
+		tokens.extend(introspect::type_ids(&options, &item)?);
+	}
+
+	if options.tags {
+		for group in &groups {
+			tokens.extend(introspect::tags(&group.item, &group.variants, &[]));
+		}
+
+		tokens.extend(introspect::tags(&item, &variants, &group_names));
+	}
+
+	if options.type_name {
+		for group in &groups {
+			tokens.extend(introspect::type_name(&group.item, &[]));
+		}
+
+		tokens.extend(introspect::type_name(&item, &group_names));
+	}
+
+	if let Some(ratio) = options.variant_sizes {
+		tokens.extend(introspect::variant_sizes(&item, ratio)?);
+	}
+
+	if options.metadata {
+		tokens.extend(metadata(&item, &variants));
+	}
+
+	// Constants tracking files by absolute path are kept out of pregenerated expansions.
+	let mut tracking = TokenStream::new();
+
+	if options.path_env.is_some() {
+		tracking.extend(track_files(&variants));
+	}
+
+	if let Some(path) = &options.registry_file {
+		tracking.extend(registry_file::track(path));
+	}
+
+	let payloads: Vec<TokenStream> = variants
+		.iter()
+		.chain(groups.iter().flat_map(|group| &group.variants))
+		.map(Variant::payload)
+		.collect();
+
+	if let Some(bounds) = &options.must_impl {
+		tokens.extend(assertions::must_impl(&item, bounds, &payloads));
+	}
+
+	if options.assert_send_sync {
+		tokens.extend(assertions::send_sync(&item, &payloads));
+	}
+
+	if options.inventory {
+		tokens.extend(inventory::collect(&item));
+	}
+
+	if let Some(path) = &options.lock {
+		let discovered: Vec<&Variant> = variants
+			.iter()
+			.chain(groups.iter().flat_map(|group| &group.variants))
+			.collect();
+
+		tracking.extend(lock::check(&item.ident, &discovered, path)?);
+	}
+
+	if let Some(path) = &options.manifest {
+		manifest::write(&options, &item, &variants, path.as_deref())?;
+	}
+
+	if variants
+		.iter()
+		.chain(groups.iter().flat_map(|group| &group.variants))
+		.any(|variant| variant.args.deprecated.is_some())
+	{
+		tokens = allow_deprecated(tokens)?;
+	}
+
+	if options.hide_helpers {
+		tokens = hide_helpers(&item.ident, tokens)?;
+	}
+
+	if let Some(mod_name) = &options.mod_name {
+		tokens = wrap_module(mod_name, &vis, &item.ident, tokens);
+	}
+
+	if options.debug {
+		debug::dump(&item.ident, &tokens)?;
+	}
+
+	if let Some(path) = &options.pregenerated {
+		tokens = pregenerated::check(&item.ident, &tokens, path)?;
+	}
+
+	tokens.extend(tracking);
+
+	// Registrations for enums that nothing in the crate builds are most likely typos.
+	if options.strict_targets {
+		let root = scan_root(&options, file)?;
+
+		check::check(&root, &options, registrations, Some(&item.ident))?;
+	} else if options.warn_targets {
+		let root = scan_root(&options, file)?;
+
+		tokens.extend(check::warn(&root, &options, registrations, &item.ident)?);
+	}
+
+	if let Some(path) = &options.stats {
+		stats::report(&item.ident, &stats::take(), path.as_deref())?;
+	}
+
+	Ok(tokens)
+}
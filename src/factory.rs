@@ -0,0 +1,488 @@
+use std::cmp::Reverse;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Fields, GenericParam, ItemEnum, Lifetime, parse_quote};
+
+use crate::{
+	case::{to_screaming_snake_case, to_snake_case},
+	options::Options,
+	variant::Variant,
+};
+
+/// Generates the expression constructing `variant` from the `Default` impls of its fields through
+/// `construct`.
+fn construct(variant: &syn::Variant) -> TokenStream {
+	let variant_ident = &variant.ident;
+
+	match &variant.fields {
+		Fields::Named(named) => {
+			let idents = named.named.iter().map(|field| &field.ident);
+			let types = named.named.iter().map(|field| &field.ty);
+
+			quote!(Self::#variant_ident { #(#idents: construct::<#types>()),* })
+		}
+		Fields::Unnamed(unnamed) => {
+			let types = unnamed.unnamed.iter().map(|field| &field.ty);
+
+			quote!(Self::#variant_ident(#(construct::<#types>()),*))
+		}
+		Fields::Unit => quote!(Self::#variant_ident),
+	}
+}
+
+/// Generates the helper constructing values from their `Default` impls, reporting the types
+/// lacking one by name.
+fn construct_helper(item: &ItemEnum) -> TokenStream {
+	let message = format!(
+		"`{{Self}}` doesn't implement `Default`, which every variant of `{}` must",
+		item.ident
+	);
+
+	quote! {
+		#[diagnostic::on_unimplemented(message = #message)]
+		trait DefaultVariant: ::core::default::Default {}
+
+		impl<T: ::core::default::Default> DefaultVariant for T {}
+
+		fn construct<T: DefaultVariant>() -> T {
+			T::default()
+		}
+	}
+}
+
+/// Generates `all_default()`, constructing every variant from the `Default` impls of its fields.
+pub(crate) fn all_default(item: &ItemEnum) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let helper = construct_helper(item);
+	let values = item.variants.iter().map(construct);
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns every variant, constructed from the `Default` impls of their fields.
+			pub fn all_default() -> impl ::core::iter::Iterator<Item = Self> {
+				#helper
+
+				::core::iter::IntoIterator::into_iter([#(#values),*])
+			}
+		}
+	}
+}
+
+/// Generates `impl Arbitrary` for the enum, picking a variant and building its fields from their
+/// own `Arbitrary` impls.
+pub(crate) fn arbitrary(item: &ItemEnum) -> TokenStream {
+	let ident = &item.ident;
+	let lifetime: Lifetime = parse_quote!('arbitrary);
+	let mut generics = item.generics.clone();
+	let types: Vec<_> = generics
+		.type_params()
+		.map(|param| param.ident.clone())
+		.collect();
+
+	for ty in types {
+		generics
+			.make_where_clause()
+			.predicates
+			.push(parse_quote!(#ty: ::arbitrary::Arbitrary<#lifetime>));
+	}
+
+	generics
+		.params
+		.insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+
+	let (impl_generics, _, where_clause) = generics.split_for_impl();
+	let (_, ty_generics, _) = item.generics.split_for_impl();
+	let last = item.variants.len() as u32 - 1;
+	let arms = item.variants.iter().enumerate().map(|(index, variant)| {
+		let index = index as u32;
+		let variant_ident = &variant.ident;
+		let value = quote!(::arbitrary::Arbitrary::arbitrary(u)?);
+		let value = match &variant.fields {
+			Fields::Named(named) => {
+				let idents = named.named.iter().map(|field| &field.ident);
+
+				quote!(Self::#variant_ident { #(#idents: #value),* })
+			}
+			Fields::Unnamed(unnamed) => {
+				let values = unnamed.unnamed.iter().map(|_| &value);
+
+				quote!(Self::#variant_ident(#(#values),*))
+			}
+			Fields::Unit => quote!(Self::#variant_ident),
+		};
+
+		quote!(#index => #value)
+	});
+
+	quote! {
+		impl #impl_generics ::arbitrary::Arbitrary<#lifetime> for #ident #ty_generics #where_clause {
+			fn arbitrary(u: &mut ::arbitrary::Unstructured<#lifetime>) -> ::arbitrary::Result<Self> {
+				::core::result::Result::Ok(match u.int_in_range(0..=#last)? {
+					#(#arms,)*
+					_ => ::core::unreachable!(),
+				})
+			}
+		}
+	}
+}
+
+/// Generates `arbitrary_<enum>()`, returning a proptest strategy picking any variant and building
+/// its fields from their own `Arbitrary` impls.
+pub(crate) fn proptest(item: &ItemEnum) -> syn::Result<TokenStream> {
+	let vis = &item.vis;
+	let ident = &item.ident;
+
+	if !item.generics.params.is_empty() {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the proptest parameter doesn't support generic enums like {}",
+				ident
+			),
+		));
+	}
+
+	let fn_ident = format_ident!("arbitrary_{}", to_snake_case(&ident.to_string()));
+	let doc = format!(
+		"Returns a proptest strategy generating every variant of [`{}`].",
+		ident
+	);
+	let strategies = item.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+		let types = variant.fields.iter().map(|field| &field.ty);
+		let bindings: Vec<_> = (0..variant.fields.len())
+			.map(|index| format_ident!("field{}", index))
+			.collect();
+		let value = match &variant.fields {
+			Fields::Named(named) => {
+				let idents = named.named.iter().map(|field| &field.ident);
+
+				quote!(#ident::#variant_ident { #(#idents: #bindings),* })
+			}
+			Fields::Unnamed(_) => quote!(#ident::#variant_ident(#(#bindings),*)),
+			Fields::Unit => quote!(#ident::#variant_ident),
+		};
+
+		quote! {
+			::proptest::strategy::Strategy::prop_map(
+				::proptest::arbitrary::any::<(#(#types,)*)>(),
+				|(#(#bindings,)*)| #value,
+			)
+		}
+	});
+
+	Ok(quote! {
+		#[doc = #doc]
+		#vis fn #fn_ident() -> impl ::proptest::strategy::Strategy<Value = #ident> {
+			::proptest::prop_oneof![#(#strategies),*]
+		}
+	})
+}
+
+/// Generates the const helpers [`module_path`] relies on.
+pub(crate) fn module_path_helpers() -> TokenStream {
+	quote! {
+		const fn parent_len(path: &str, levels: usize) -> usize {
+			let bytes = path.as_bytes();
+			let mut end = bytes.len();
+			let mut levels = levels;
+
+			while levels > 0 {
+				while end >= 2 && !(bytes[end - 2] == b':' && bytes[end - 1] == b':') {
+					end -= 1;
+				}
+
+				end = end.saturating_sub(2);
+				levels -= 1;
+			}
+
+			end
+		}
+
+		const fn join<const N: usize>(path: &str, len: usize, suffix: &str) -> [u8; N] {
+			let mut buf = [0; N];
+			let mut index = 0;
+
+			while index < len {
+				buf[index] = path.as_bytes()[index];
+				index += 1;
+			}
+
+			while index < N {
+				buf[index] = suffix.as_bytes()[index - len];
+				index += 1;
+			}
+
+			buf
+		}
+	}
+}
+
+/// Generates the constant expression naming the absolute path of the module `variant` was
+/// declared in, relative to the module of the enum.
+pub(crate) fn module_path(variant: &Variant) -> TokenStream {
+	let levels = variant
+		.module
+		.iter()
+		.take_while(|segment| *segment == "super")
+		.count();
+	let suffix: String = variant.module[levels..]
+		.iter()
+		.map(|segment| format!("::{}", segment))
+		.collect();
+
+	if levels == 0 {
+		return quote!(::core::concat!(::core::module_path!(), #suffix));
+	}
+
+	quote! {{
+		const LEN: usize = parent_len(::core::module_path!(), #levels);
+		const SUFFIX: &str = #suffix;
+		const BUF: [u8; LEN + SUFFIX.len()] = join(::core::module_path!(), LEN, SUFFIX);
+
+		match ::core::str::from_utf8(&BUF) {
+			::core::result::Result::Ok(path) => path,
+			::core::result::Result::Err(_) => ::core::panic!("module paths are valid UTF-8"),
+		}
+	}}
+}
+
+/// Generates the static registry describing every discovered variant, with a constructor for the
+/// variants whose payload implements `Default`.
+pub(crate) fn registry(
+	options: &Options,
+	item: &ItemEnum,
+	variants: &[Variant],
+) -> syn::Result<TokenStream> {
+	let vis = &item.vis;
+	let ident = &item.ident;
+
+	if !item.generics.params.is_empty() {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the registry parameter doesn't support generic enums like {}",
+				ident
+			),
+		));
+	}
+
+	let descriptor_ident = format_ident!("{}VariantDescriptor", ident);
+	let registry_ident = format_ident!("{}_REGISTRY", to_screaming_snake_case(&ident.to_string()));
+	let descriptor_doc = format!(
+		"Describes a discovered variant of [`{}`] at runtime.",
+		ident
+	);
+	let registry_doc = format!(
+		"Descriptors of every discovered variant of [`{}`], by decreasing priority and then in declaration order.",
+		ident
+	);
+	let helpers = module_path_helpers();
+	let config = options
+		.config
+		.as_ref()
+		.map(|config| quote!(config: &#config));
+	let constructor_ty = quote!(fn(#config) -> #ident);
+	let ignored = options.config.as_ref().map(|config| quote!(_: &#config));
+	let mut wraps = vec![];
+	let mut descriptors = vec![];
+	let mut variants: Vec<&Variant> = variants.iter().collect();
+
+	// The sort is stable, so variants with the same priority stay in declaration order.
+	variants.sort_by_key(|variant| Reverse(variant.args.priority.unwrap_or_default()));
+
+	for variant in variants {
+		let name_ident = &variant.name;
+		let name = name_ident.to_string();
+		let module_path = module_path(variant);
+		let tags = &variant.args.tags;
+		let priority = variant.args.priority.unwrap_or_default();
+		let constructor = if let Some(constructor) = &variant.args.constructor {
+			if options.unit {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"variant {} has a constructor, which isn't called for unit enums",
+						name_ident
+					),
+				));
+			}
+
+			let arg = options.config.as_ref().map(|_| quote!(config));
+
+			quote!(::core::option::Option::Some(|#config| #ident::#name_ident(#constructor(#arg))))
+		} else if options.unit {
+			quote!(::core::option::Option::Some(|#ignored| #ident::#name_ident))
+		} else {
+			let payload = variant.payload();
+
+			wraps.push(quote! {
+				impl Wrap for #payload {
+					fn wrap(self) -> #ident {
+						#ident::#name_ident(self)
+					}
+				}
+			});
+
+			quote!(Probe::<#payload>::CONSTRUCTOR)
+		};
+
+		descriptors.push(quote! {
+			#descriptor_ident {
+				name: #name,
+				module_path: #module_path,
+				tags: &[#(#tags),*],
+				priority: #priority,
+				constructor: #constructor,
+			}
+		});
+	}
+
+	Ok(quote! {
+		#[doc = #descriptor_doc]
+		#[derive(::core::clone::Clone, ::core::marker::Copy, ::core::fmt::Debug)]
+		#vis struct #descriptor_ident {
+			/// The variant name.
+			pub name: &'static str,
+			/// The path of the module the payload type is declared in.
+			pub module_path: &'static str,
+			/// The tags the variant was registered with.
+			pub tags: &'static [&'static str],
+			/// The priority the variant was registered with, zero by default.
+			pub priority: i32,
+			/// Constructs the variant through the constructor it was registered with, or the
+			/// `Default` impl of its payload, if it has either.
+			pub constructor: ::core::option::Option<#constructor_ty>,
+		}
+
+		#[doc = #registry_doc]
+		#vis static #registry_ident: &[#descriptor_ident] = {
+			#helpers
+
+			struct Probe<T>(::core::marker::PhantomData<T>);
+
+			trait Wrap {
+				fn wrap(self) -> #ident;
+			}
+
+			#(#wraps)*
+
+			// Inherent constants take precedence over trait constants, so this is only picked up
+			// when the payload implements `Default`.
+			#[allow(dead_code)]
+			trait Fallback {
+				const CONSTRUCTOR: ::core::option::Option<#constructor_ty> = ::core::option::Option::None;
+			}
+
+			impl<T> Fallback for Probe<T> {}
+
+			#[allow(dead_code)]
+			impl<T: ::core::default::Default + Wrap> Probe<T> {
+				const CONSTRUCTOR: ::core::option::Option<#constructor_ty> = ::core::option::Option::Some(|#ignored| T::default().wrap());
+			}
+
+			&[#(#descriptors),*]
+		};
+	})
+}
+
+/// Generates `from_name()`, constructing the variant with the provided name through the registry.
+pub(crate) fn from_name(options: &Options, item: &ItemEnum) -> TokenStream {
+	let ident = &item.ident;
+	let registry_ident = format_ident!("{}_REGISTRY", to_screaming_snake_case(&ident.to_string()));
+	let config = options
+		.config
+		.as_ref()
+		.map(|config| quote!(, config: &#config));
+	let arg = options.config.as_ref().map(|_| quote!(config));
+
+	quote! {
+		impl #ident {
+			/// Constructs the variant with the provided name through the constructor it was
+			/// registered with or the `Default` impl of its payload, if there is such a variant and
+			/// it has either.
+			pub fn from_name(name: &str #config) -> ::core::option::Option<Self> {
+				#registry_ident
+					.iter()
+					.find(|descriptor| descriptor.name == name)?
+					.constructor
+					.map(|constructor| constructor(#arg))
+			}
+		}
+	}
+}
+
+/// Generates `from_tagged_value()`, deserializing the payload of the variant named by a tag from a
+/// `serde_json::Value`.
+pub(crate) fn from_tagged_value(
+	options: &Options,
+	item: &ItemEnum,
+	variants: &[Variant],
+) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let error_ident = format_ident!("{}FactoryError", ident);
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let error_doc = format!(
+		"The error returned when constructing a [`{}`] from a tagged value fails.",
+		ident
+	);
+	let string_path = options.string_path();
+	let unknown = format!("unknown {} tag {{}}", ident);
+	let invalid = format!("invalid {} payload: {{}}", ident);
+	let arms = variants.iter().map(|variant| {
+		let name_ident = &variant.name;
+		let name = name_ident.to_string();
+		let payload = variant.payload();
+
+		quote! {
+			#name => ::serde_json::from_value::<#payload>(value)
+				.map(Self::#name_ident)
+				.map_err(#error_ident::Deserialize)
+		}
+	});
+
+	quote! {
+		#[doc = #error_doc]
+		#[derive(::core::fmt::Debug)]
+		#vis enum #error_ident {
+			/// The tag doesn't name a variant.
+			UnknownTag(#string_path),
+			/// The value isn't a valid payload for the variant named by the tag.
+			Deserialize(::serde_json::Error),
+		}
+
+		impl ::core::fmt::Display for #error_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				match self {
+					Self::UnknownTag(tag) => ::core::write!(f, #unknown, tag),
+					Self::Deserialize(err) => ::core::write!(f, #invalid, err),
+				}
+			}
+		}
+
+		impl ::core::error::Error for #error_ident {
+			fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+				match self {
+					Self::UnknownTag(_) => ::core::option::Option::None,
+					Self::Deserialize(err) => ::core::option::Option::Some(err),
+				}
+			}
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Deserializes `value` as the payload of the variant named by `tag`.
+			pub fn from_tagged_value(
+				tag: &str,
+				value: ::serde_json::Value,
+			) -> ::core::result::Result<Self, #error_ident> {
+				match tag {
+					#(#arms,)*
+					_ => ::core::result::Result::Err(#error_ident::UnknownTag(#string_path::from(tag))),
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,38 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, Ident, Type};
+
+/// Builds the pattern destructuring a variant with `fields`, along with the binding and type of
+/// every field in declaration order.
+pub(crate) fn destructure(fields: &Fields) -> (TokenStream, Vec<(Ident, &Type)>) {
+	match fields {
+		Fields::Named(named) => {
+			let bindings: Vec<(Ident, &Type)> = named
+				.named
+				.iter()
+				.filter_map(|field| Some((field.ident.clone()?, &field.ty)))
+				.collect();
+			let idents = bindings.iter().map(|(ident, _)| ident);
+
+			(quote!({ #(#idents),* }), bindings)
+		}
+		Fields::Unnamed(unnamed) => {
+			let bindings: Vec<(Ident, &Type)> = unnamed
+				.unnamed
+				.iter()
+				.enumerate()
+				.map(|(index, field)| (format_ident!("field_{}", index), &field.ty))
+				.collect();
+			let idents = bindings.iter().map(|(ident, _)| ident);
+
+			(quote!((#(#idents),*)), bindings)
+		}
+		Fields::Unit => (TokenStream::new(), vec![]),
+	}
+}
+
+/// The `self` a method taking it by reference matches against one arm per variant, dereferenced
+/// when the enum has no variants, as a reference to an enum without variants isn't uninhabited.
+pub(crate) fn matched_self(empty: bool) -> TokenStream {
+	if empty { quote!(*self) } else { quote!(self) }
+}
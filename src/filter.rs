@@ -0,0 +1,62 @@
+use syn::Visibility;
+
+use crate::{options::Options, stats, variant::Variant};
+
+/// Whether `name` matches `pattern`, a substring that is anchored to the start of the name when
+/// prefixed with `^`, and to its end when suffixed with `$`.
+fn matches(pattern: &str, name: &str) -> bool {
+	let (start, pattern) = match pattern.strip_prefix('^') {
+		Some(pattern) => (true, pattern),
+		None => (false, pattern),
+	};
+	let (end, pattern) = match pattern.strip_suffix('$') {
+		Some(pattern) => (true, pattern),
+		None => (false, pattern),
+	};
+
+	match (start, end) {
+		(true, true) => name == pattern,
+		(true, false) => name.starts_with(pattern),
+		(false, true) => name.ends_with(pattern),
+		(false, false) => name.contains(pattern),
+	}
+}
+
+/// Drops the discovered types rejected by the `only_pub`, `include` and `exclude` parameters,
+/// counting those dropped by each.
+pub(crate) fn retain(options: &Options, variants: &mut Vec<Variant>) {
+	stats::record(|stats| stats.discovered += variants.len());
+
+	variants.retain(|variant| {
+		let name = variant.ident.to_string();
+
+		if options.only_pub && !matches!(variant.vis, Visibility::Public(_)) {
+			stats::record(|stats| stats.only_pub += 1);
+
+			return false;
+		}
+
+		if !options.include.is_empty()
+			&& !options
+				.include
+				.iter()
+				.any(|pattern| matches(pattern, &name))
+		{
+			stats::record(|stats| stats.include += 1);
+
+			return false;
+		}
+
+		if options
+			.exclude
+			.iter()
+			.any(|pattern| matches(pattern, &name))
+		{
+			stats::record(|stats| stats.exclude += 1);
+
+			return false;
+		}
+
+		true
+	});
+}
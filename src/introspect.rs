@@ -0,0 +1,536 @@
+use std::path::Path;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Fields, Generics, Ident, ItemEnum, Type, WherePredicate, parse_quote};
+
+use crate::{
+	factory, fields::matched_self, options::Options, scan::display_path, variant::Variant,
+};
+
+/// Generates `VARIANT_NAMES` and `name()` for an enum with the given variant names.
+fn names_impl(ident: &Ident, generics: &Generics, names: &[&Ident]) -> TokenStream {
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let strings: Vec<String> = names.iter().map(ToString::to_string).collect();
+	let matched = matched_self(names.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// The names of every variant, in declaration order.
+			pub const VARIANT_NAMES: &'static [&'static str] = &[#(#strings),*];
+
+			/// Returns the name of this variant.
+			pub fn name(&self) -> &'static str {
+				match #matched {
+					#(Self::#names { .. } => #strings,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates variant name introspection for the enum, and its kind companion if enabled.
+pub(crate) fn names(options: &Options, item: &ItemEnum) -> TokenStream {
+	let names: Vec<&Ident> = item.variants.iter().map(|variant| &variant.ident).collect();
+	let mut tokens = names_impl(&item.ident, &item.generics, &names);
+
+	if options.kind {
+		tokens.extend(names_impl(
+			&options.kind_ident(&item.ident),
+			&Generics::default(),
+			&names,
+		));
+	}
+
+	tokens
+}
+
+/// Generates `COUNT` and `variant_index()` for an enum with the given variant names.
+fn index_impl(ident: &Ident, generics: &Generics, names: &[&Ident]) -> TokenStream {
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let count = names.len();
+	let indices = 0..count;
+	let matched = matched_self(names.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// The number of variants.
+			pub const COUNT: usize = #count;
+
+			/// Returns the position of this variant in declaration order, below `COUNT`.
+			pub fn variant_index(&self) -> usize {
+				match #matched {
+					#(Self::#names { .. } => #indices,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates variant counting and indexing for the enum, and its kind companion if enabled.
+pub(crate) fn index(options: &Options, item: &ItemEnum) -> TokenStream {
+	let names: Vec<&Ident> = item.variants.iter().map(|variant| &variant.ident).collect();
+	let mut tokens = index_impl(&item.ident, &item.generics, &names);
+
+	if options.kind {
+		tokens.extend(index_impl(
+			&options.kind_ident(&item.ident),
+			&Generics::default(),
+			&names,
+		));
+	}
+
+	tokens
+}
+
+/// Generates a `Display` impl writing the variant name for an enum with the given variant names.
+fn display_impl(
+	options: &Options,
+	ident: &Ident,
+	generics: &Generics,
+	names: &[&Ident],
+) -> TokenStream {
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let strings = names.iter().map(|name| options.external_name(name));
+	let matched = matched_self(names.is_empty());
+
+	quote! {
+		impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				match #matched {
+					#(Self::#names { .. } => f.write_str(#strings),)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `Display` for the enum, and its kind companion if enabled.
+pub(crate) fn display(options: &Options, item: &ItemEnum) -> TokenStream {
+	let names: Vec<&Ident> = item.variants.iter().map(|variant| &variant.ident).collect();
+	let mut tokens = display_impl(options, &item.ident, &item.generics, &names);
+
+	if options.kind {
+		tokens.extend(display_impl(
+			options,
+			&options.kind_ident(&item.ident),
+			&Generics::default(),
+			&names,
+		));
+	}
+
+	tokens
+}
+
+/// Generates `Debug` for the enum, printing the enum and variant names, followed by the path of the
+/// payload type and the payload itself for discovered variants, e.g.
+/// `Animal::Dog (zoo::canine::Dog) Dog { age: 3 }`. Grouped variants forward to their inner enum,
+/// and other variants print their fields.
+pub(crate) fn debug(item: &ItemEnum, variants: &[Variant], groups: &[Ident]) -> TokenStream {
+	let ident = &item.ident;
+	let helpers = factory::module_path_helpers();
+	let arms = item.variants.iter().map(|enum_variant| {
+		let variant_ident = &enum_variant.ident;
+		let label = format!("{}::{}", ident, variant_ident);
+
+		if groups.contains(variant_ident) {
+			return quote!(Self::#variant_ident(inner) => ::core::fmt::Debug::fmt(inner, f));
+		}
+
+		if let Some(variant) = variants
+			.iter()
+			.find(|variant| variant.name == *variant_ident)
+		{
+			// Stored types may be declared in any module, so they're named by the compiler.
+			let location = match &variant.args.store {
+				Some(_) => {
+					let payload = variant.payload();
+
+					quote!(::core::write!(f, "{} ({})", #label, ::core::any::type_name::<#payload>())?;)
+				}
+				None => {
+					let path = factory::module_path(variant);
+					let type_name = variant.ident.to_string();
+
+					quote! {
+						const PATH: &str = #path;
+
+						::core::write!(f, "{} ({}::{})", #label, PATH, #type_name)?;
+					}
+				}
+			};
+			let (pattern, payload) = match &enum_variant.fields {
+				Fields::Unit => (quote!(), quote!(::core::fmt::Result::Ok(()))),
+				_ => (
+					quote!((inner)),
+					quote! {
+						f.write_str(" ")?;
+						::core::fmt::Debug::fmt(inner, f)
+					},
+				),
+			};
+
+			return quote! {
+				Self::#variant_ident #pattern => {
+					#location
+					#payload
+				}
+			};
+		}
+
+		match &enum_variant.fields {
+			Fields::Named(fields) => {
+				let names: Vec<&Ident> = fields
+					.named
+					.iter()
+					.filter_map(|field| field.ident.as_ref())
+					.collect();
+				let strings = names.iter().map(ToString::to_string);
+
+				quote! {
+					Self::#variant_ident { #(#names),* } => f
+						.debug_struct(#label)
+						#(.field(#strings, #names))*
+						.finish()
+				}
+			}
+			Fields::Unnamed(fields) => {
+				let names: Vec<Ident> = (0..fields.unnamed.len())
+					.map(|index| format_ident!("field{}", index))
+					.collect();
+
+				quote! {
+					Self::#variant_ident(#(#names),*) => f
+						.debug_tuple(#label)
+						#(.field(#names))*
+						.finish()
+				}
+			}
+			Fields::Unit => quote!(Self::#variant_ident => f.write_str(#label)),
+		}
+	});
+	let mut generics = item.generics.clone();
+
+	// Generic payloads are only printable for some arguments.
+	if !generics.params.is_empty() {
+		let predicates = item
+			.variants
+			.iter()
+			.flat_map(|variant| &variant.fields)
+			.map(|field| -> WherePredicate {
+				let ty = &field.ty;
+
+				parse_quote!(#ty: ::core::fmt::Debug)
+			});
+
+		generics.make_where_clause().predicates.extend(predicates);
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				#helpers
+
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `module_path()`, returning the path of the module each variant's payload is declared
+/// in. Grouped variants forward to their inner enum, and other variants report the enum's module.
+pub(crate) fn module_path(item: &ItemEnum, variants: &[Variant], groups: &[Ident]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let helpers = factory::module_path_helpers();
+	let arms = item.variants.iter().map(|enum_variant| {
+		let variant_ident = &enum_variant.ident;
+
+		if groups.contains(variant_ident) {
+			return quote!(Self::#variant_ident(inner) => inner.module_path());
+		}
+
+		let path = match variants
+			.iter()
+			.find(|variant| variant.name == *variant_ident)
+		{
+			Some(variant) => factory::module_path(variant),
+			None => quote!(::core::module_path!()),
+		};
+
+		quote! {
+			Self::#variant_ident { .. } => {
+				const PATH: &str = #path;
+
+				PATH
+			}
+		}
+	});
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the path of the module the payload of this variant is declared in.
+			pub fn module_path(&self) -> &'static str {
+				#helpers
+
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `source_location()`, returning the file and line each variant's payload was
+/// discovered at. Grouped variants forward to their inner enum, and other variants report the
+/// location of the enum.
+pub(crate) fn source_location(
+	item: &ItemEnum,
+	variants: &[Variant],
+	groups: &[Ident],
+	file: &Path,
+) -> TokenStream {
+	let ident = &item.ident;
+	let enum_file = display_path(file);
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let arms = item.variants.iter().map(|enum_variant| {
+		let variant_ident = &enum_variant.ident;
+
+		if groups.contains(variant_ident) {
+			return quote!(Self::#variant_ident(inner) => inner.source_location());
+		}
+
+		match variants
+			.iter()
+			.find(|variant| variant.name == *variant_ident)
+		{
+			Some(variant) => {
+				let file = display_path(&variant.file);
+				let line = variant.line as u32;
+
+				quote!(Self::#variant_ident { .. } => (#file, #line))
+			}
+			None => quote!(Self::#variant_ident { .. } => (#enum_file, ::core::line!())),
+		}
+	});
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the file, relative to the crate root, and the line the payload of this
+			/// variant is declared at.
+			pub fn source_location(&self) -> (&'static str, u32) {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `tags()`, returning the tags each variant was registered with. Grouped variants
+/// forward to their inner enum, and other variants have no tags.
+pub(crate) fn tags(item: &ItemEnum, variants: &[Variant], groups: &[Ident]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let arms = item.variants.iter().map(|enum_variant| {
+		let variant_ident = &enum_variant.ident;
+
+		if groups.contains(variant_ident) {
+			return quote!(Self::#variant_ident(inner) => inner.tags());
+		}
+
+		let tags = variants
+			.iter()
+			.find(|variant| variant.name == *variant_ident)
+			.into_iter()
+			.flat_map(|variant| &variant.args.tags);
+
+		quote!(Self::#variant_ident { .. } => &[#(#tags),*])
+	});
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the tags this variant was registered with.
+			pub fn tags(&self) -> &'static [&'static str] {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `type_name()`, returning the full name of the type held by each variant. Grouped
+/// variants forward to their inner enum, and variants not holding a single value report the enum.
+pub(crate) fn type_name(item: &ItemEnum, groups: &[Ident]) -> TokenStream {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let arms = item.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+
+		if groups.contains(variant_ident) {
+			return quote!(Self::#variant_ident(inner) => inner.type_name());
+		}
+
+		match &variant.fields {
+			Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+				let ty = &fields.unnamed[0].ty;
+
+				quote!(Self::#variant_ident(_) => ::core::any::type_name::<#ty>())
+			}
+			_ => quote!(Self::#variant_ident { .. } => ::core::any::type_name::<Self>()),
+		}
+	});
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the full name of the type held by this variant.
+			pub fn type_name(&self) -> &'static str {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
+
+/// Generates `type_ids()` mapping the `TypeId` of every payload to its kind, and the `kind_of()` and
+/// `holds()` lookups built on it. Variants not holding a single value have no entry.
+pub(crate) fn type_ids(options: &Options, item: &ItemEnum) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+
+	if !item.generics.params.is_empty() {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the type_ids parameter doesn't support generic enums like {}",
+				ident
+			),
+		));
+	}
+
+	let kind_ident = options.kind_ident(ident);
+	let (names, payloads): (Vec<&Ident>, Vec<&Type>) = item
+		.variants
+		.iter()
+		.filter_map(|variant| match &variant.fields {
+			Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+				Some((&variant.ident, &fields.unnamed[0].ty))
+			}
+			_ => None,
+		})
+		.unzip();
+	let count = names.len();
+
+	Ok(quote! {
+		impl #ident {
+			/// Returns the `TypeId` of every payload along with the kind of the variant holding it,
+			/// in declaration order.
+			pub fn type_ids() -> [(::core::any::TypeId, #kind_ident); #count] {
+				[#((::core::any::TypeId::of::<#payloads>(), #kind_ident::#names)),*]
+			}
+
+			/// Returns the kind of the variant holding `T`, if any.
+			pub fn kind_of<T: 'static>() -> ::core::option::Option<#kind_ident> {
+				::core::iter::IntoIterator::into_iter(Self::type_ids())
+					.find(|(id, _)| *id == ::core::any::TypeId::of::<T>())
+					.map(|(_, kind)| kind)
+			}
+
+			/// Returns `true` if this variant holds a `T`.
+			pub fn holds<T: 'static>(&self) -> bool {
+				Self::kind_of::<T>() == ::core::option::Option::Some(self.kind())
+			}
+		}
+	})
+}
+
+/// Generates `VARIANT_SIZES`, holding the size of the fields of each variant, and a deprecation
+/// warning when the largest variant is more than `ratio` times larger than the next largest one.
+pub(crate) fn variant_sizes(item: &ItemEnum, ratio: Option<usize>) -> syn::Result<TokenStream> {
+	let ident = &item.ident;
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let sizes = item.variants.iter().map(|variant| {
+		let name = variant.ident.to_string();
+		let types = variant.fields.iter().map(|field| &field.ty);
+
+		quote!((#name, ::core::mem::size_of::<(#(#types,)*)>()))
+	});
+	let mut tokens = quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// The names of every variant along with the size of their fields, in declaration
+			/// order.
+			pub const VARIANT_SIZES: &'static [(&'static str, usize)] = &[#(#sizes),*];
+		}
+	};
+
+	let Some(ratio) = ratio else {
+		return Ok(tokens);
+	};
+
+	if !item.generics.params.is_empty() {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the size ratio of the variant_sizes parameter doesn't support generic enums like {}",
+				ident
+			),
+		));
+	}
+
+	let note = format!(
+		"a variant of {} is more than {} times larger than the others, consider boxing it; see {}::VARIANT_SIZES",
+		ident, ratio, ident
+	);
+
+	// Deprecation warnings are the only warnings generated code can trigger, so the check picks
+	// between a deprecated and a regular function depending on the sizes.
+	tokens.extend(quote! {
+		const _: () = {
+			const fn is_oversized(sizes: &[(&str, usize)], ratio: usize) -> bool {
+				let mut largest = 0;
+				let mut next = 0;
+				let mut index = 0;
+
+				while index < sizes.len() {
+					let size = sizes[index].1;
+
+					if size > largest {
+						next = largest;
+						largest = size;
+					} else if size > next {
+						next = size;
+					}
+
+					index += 1;
+				}
+
+				next > 0 && largest > next.saturating_mul(ratio)
+			}
+
+			struct SizeCheck<const OVERSIZED: bool>;
+
+			impl SizeCheck<false> {
+				const fn check() {}
+			}
+
+			impl SizeCheck<true> {
+				#[deprecated(note = #note)]
+				const fn check() {}
+			}
+
+			SizeCheck::<{ is_oversized(#ident::VARIANT_SIZES, #ratio) }>::check();
+		};
+	});
+
+	Ok(tokens)
+}
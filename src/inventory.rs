@@ -0,0 +1,63 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, ItemEnum};
+
+use crate::variant::VariantArgs;
+
+/// Name of the registration type collected for the enum named `enum_ident`.
+fn registration_ident(enum_ident: &Ident) -> Ident {
+	format_ident!("{}Registration", enum_ident)
+}
+
+/// Generates the registration type collected with `inventory` for the enum, and its `iter()`
+/// function iterating over every submitted registration.
+pub(crate) fn collect(item: &ItemEnum) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let registration_ident = registration_ident(ident);
+	let doc = format!(
+		"A type registered as a variant of [`{}`], submitted at runtime by any crate linked into \
+		 the binary.",
+		ident
+	);
+
+	quote! {
+		#[doc = #doc]
+		#[derive(::core::clone::Clone, ::core::marker::Copy, ::core::fmt::Debug)]
+		#vis struct #registration_ident {
+			/// The name of the registered type.
+			pub type_name: &'static str,
+			/// The path of the module the type is declared in.
+			pub module_path: &'static str,
+			/// Key/value pairs declared with `meta(...)` on the registration.
+			pub meta: &'static [(&'static str, &'static str)],
+		}
+
+		::inventory::collect!(#registration_ident);
+
+		impl #registration_ident {
+			/// Iterates over every registration submitted for the enum, in no particular order.
+			pub fn iter() -> impl ::core::iter::Iterator<Item = &'static Self> {
+				::inventory::iter::<Self>.into_iter()
+			}
+		}
+	}
+}
+
+/// Generates the `inventory` submission registering the type named `ident` for the enum named by
+/// `args`.
+pub(crate) fn submit(args: &VariantArgs, ident: &Ident) -> TokenStream {
+	let registration_ident = registration_ident(&args.enum_name);
+	let type_name = ident.to_string();
+	let meta = args.meta.iter().map(|(key, value)| quote!((#key, #value)));
+
+	quote! {
+		::inventory::submit! {
+			#registration_ident {
+				type_name: #type_name,
+				module_path: ::core::module_path!(),
+				meta: &[#(#meta),*],
+			}
+		}
+	}
+}
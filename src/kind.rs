@@ -0,0 +1,291 @@
+use std::cmp::Reverse;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, ItemEnum};
+
+use crate::{fields::matched_self, options::Options, variant::Variant};
+
+/// Generates the field-less companion enum naming each variant, and the `kind()` method returning
+/// it.
+pub(crate) fn kind(options: &Options, item: &ItemEnum) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let kind_ident = options.kind_ident(ident);
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let repr = options.repr.as_ref().map(|repr| quote!(#[repr(#repr)]));
+	let doc = format!("The kinds of [`{}`] variants, without their fields.", ident);
+	let strum = &options.strum;
+	let strum = (!strum.is_empty()).then(|| quote!(#[derive(#(::strum::#strum),*)]));
+	let clap = options.clap.then(|| {
+		quote! {
+			#[derive(::clap::ValueEnum)]
+			#[value(rename_all = "kebab-case")]
+		}
+	});
+	let kinds = item.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+		let attrs = variant
+			.attrs
+			.iter()
+			.filter(|attr| attr.path().is_ident("strum"));
+		let discriminant = variant
+			.discriminant
+			.as_ref()
+			.map(|(_, discriminant)| quote!(= #discriminant));
+
+		quote!(#(#attrs)* #variant_ident #discriminant)
+	});
+	let arms = item.variants.iter().map(|variant| {
+		let variant_ident = &variant.ident;
+
+		quote!(Self::#variant_ident { .. } => #kind_ident::#variant_ident)
+	});
+	let names: Vec<&Ident> = item.variants.iter().map(|variant| &variant.ident).collect();
+	let count = names.len();
+	let indices = 0..count;
+	let discriminants = options
+		.repr
+		.as_ref()
+		.map(|repr| discriminants(&kind_ident, item, repr));
+	let matched = matched_self(item.variants.is_empty());
+
+	quote! {
+		#[doc = #doc]
+		#repr
+		#[derive(
+			::core::clone::Clone,
+			::core::marker::Copy,
+			::core::fmt::Debug,
+			::core::cmp::PartialEq,
+			::core::cmp::Eq,
+			::core::hash::Hash,
+		)]
+		#strum
+		#clap
+		#vis enum #kind_ident {
+			#(#kinds),*
+		}
+
+		impl #kind_ident {
+			/// Every kind, in declaration order.
+			pub const ALL: [Self; #count] = [#(Self::#names),*];
+
+			/// Returns an iterator over every kind, in declaration order.
+			pub fn iter() -> impl ::core::iter::Iterator<Item = Self> {
+				::core::iter::IntoIterator::into_iter(Self::ALL)
+			}
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns the kind of this variant.
+			pub fn kind(&self) -> #kind_ident {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+
+		// Kinds are ordered by declaration rather than by discriminant.
+		impl ::core::cmp::Ord for #kind_ident {
+			fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+				let position = |kind: &Self| match *kind {
+					#(Self::#names => #indices,)*
+				};
+
+				::core::cmp::Ord::cmp(&position(self), &position(other))
+			}
+		}
+
+		impl ::core::cmp::PartialOrd for #kind_ident {
+			fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+				::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+			}
+		}
+
+		impl #impl_generics ::core::convert::From<&#ident #ty_generics> for #kind_ident #where_clause {
+			fn from(value: &#ident #ty_generics) -> Self {
+				value.kind()
+			}
+		}
+
+		#discriminants
+	}
+}
+
+/// Generates the `as_<repr>()` method returning the discriminant of each kind, and
+/// `TryFrom<repr>` mapping discriminants back to kinds, returning unknown ones as the error.
+fn discriminants(kind_ident: &Ident, item: &ItemEnum, repr: &Ident) -> TokenStream {
+	let method = format_ident!("as_{}", repr);
+	let doc = format!("Returns the `{}` discriminant of this kind.", repr);
+	let names = item.variants.iter().map(|variant| &variant.ident);
+
+	quote! {
+		impl #kind_ident {
+			#[doc = #doc]
+			pub const fn #method(self) -> #repr {
+				self as #repr
+			}
+		}
+
+		impl ::core::convert::TryFrom<#repr> for #kind_ident {
+			type Error = #repr;
+
+			fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+				#(
+					if value == Self::#names as #repr {
+						return ::core::result::Result::Ok(Self::#names);
+					}
+				)*
+
+				::core::result::Result::Err(value)
+			}
+		}
+	}
+}
+
+/// Generates `kinds_by_priority()`, listing every kind by decreasing priority, with those of
+/// variants registered without one at zero.
+pub(crate) fn by_priority(options: &Options, item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let ident = &item.ident;
+	let kind_ident = options.kind_ident(ident);
+	let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+	let mut names: Vec<(i32, &Ident)> = item
+		.variants
+		.iter()
+		.map(|enum_variant| {
+			let priority = variants
+				.iter()
+				.find(|variant| variant.name == enum_variant.ident)
+				.and_then(|variant| variant.args.priority)
+				.unwrap_or_default();
+
+			(priority, &enum_variant.ident)
+		})
+		.collect();
+
+	// The sort is stable, so kinds with the same priority stay in declaration order.
+	names.sort_by_key(|(priority, _)| Reverse(*priority));
+
+	let count = names.len();
+	let names = names.iter().map(|(_, name)| name);
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns every kind by decreasing priority, and in declaration order among kinds
+			/// with the same priority.
+			pub const fn kinds_by_priority() -> [#kind_ident; #count] {
+				[#(#kind_ident::#names),*]
+			}
+		}
+	}
+}
+
+/// Generates `from_name()` and `FromStr` for the companion kind enum.
+pub(crate) fn from_str(options: &Options, item: &ItemEnum) -> TokenStream {
+	let vis = &item.vis;
+	let kind_ident = options.kind_ident(&item.ident);
+	let error_ident = format_ident!("Parse{}Error", kind_ident);
+	let names: Vec<&Ident> = item.variants.iter().map(|variant| &variant.ident).collect();
+	let strings: Vec<String> = names
+		.iter()
+		.map(|name| options.external_name(name))
+		.collect();
+	let message = format!("unknown {} name", kind_ident);
+	let error_doc = format!(
+		"The error returned when parsing an unknown [`{}`] name.",
+		kind_ident
+	);
+	let lookup = if options.case_insensitive {
+		quote! {
+			#(
+				if name.eq_ignore_ascii_case(#strings) {
+					return ::core::option::Option::Some(Self::#names);
+				}
+			)*
+
+			::core::option::Option::None
+		}
+	} else {
+		quote! {
+			match name {
+				#(#strings => ::core::option::Option::Some(Self::#names),)*
+				_ => ::core::option::Option::None,
+			}
+		}
+	};
+
+	quote! {
+		#[doc = #error_doc]
+		#[derive(
+			::core::clone::Clone,
+			::core::marker::Copy,
+			::core::fmt::Debug,
+			::core::cmp::PartialEq,
+			::core::cmp::Eq,
+		)]
+		#vis struct #error_ident;
+
+		impl ::core::fmt::Display for #error_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				f.write_str(#message)
+			}
+		}
+
+		impl ::core::error::Error for #error_ident {}
+
+		impl #kind_ident {
+			/// Returns the kind with the provided variant name, if any.
+			pub fn from_name(name: &str) -> ::core::option::Option<Self> {
+				#lookup
+			}
+		}
+
+		impl ::core::str::FromStr for #kind_ident {
+			type Err = #error_ident;
+
+			fn from_str(name: &str) -> ::core::result::Result<Self, Self::Err> {
+				Self::from_name(name).ok_or(#error_ident)
+			}
+		}
+	}
+}
+
+/// Generates `same_kind()` and the wrapper comparing and hashing values only by their variant.
+pub(crate) fn same_kind(item: &ItemEnum) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let wrapper_ident = format_ident!("{}KindEq", ident);
+	let generics = &item.generics;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let doc = format!(
+		"Wraps [`{}`] to compare and hash it only by its variant, ignoring its fields.",
+		ident
+	);
+
+	quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Returns `true` if `other` is the same variant as this, regardless of their fields.
+			pub fn same_kind(&self, other: &Self) -> bool {
+				::core::mem::discriminant(self) == ::core::mem::discriminant(other)
+			}
+		}
+
+		#[doc = #doc]
+		#vis struct #wrapper_ident #generics (pub #ident #ty_generics) #where_clause;
+
+		impl #impl_generics ::core::cmp::PartialEq for #wrapper_ident #ty_generics #where_clause {
+			fn eq(&self, other: &Self) -> bool {
+				self.0.same_kind(&other.0)
+			}
+		}
+
+		impl #impl_generics ::core::cmp::Eq for #wrapper_ident #ty_generics #where_clause {}
+
+		impl #impl_generics ::core::hash::Hash for #wrapper_ident #ty_generics #where_clause {
+			fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+				::core::hash::Hash::hash(&::core::mem::discriminant(&self.0), state);
+			}
+		}
+	}
+}
@@ -43,75 +43,508 @@
 //! enum Animal {}
 //! ```
 
-use std::{ffi::OsStr, fs};
+mod accessors;
+mod assertions;
+mod borrow;
+mod cache;
+mod case;
+mod check;
+mod debug;
+mod decl;
+mod dispatch;
+mod edition;
+mod error;
+mod expand;
+mod factory;
+mod fields;
+mod filter;
+mod introspect;
+mod inventory;
+mod kind;
+mod lock;
+mod macros;
+mod manifest;
+mod options;
+mod pregenerated;
+mod registry_file;
+mod scan;
+mod stats;
+mod variant;
+mod visitor;
+
+use std::path::Path;
 
 use proc_macro::{Span, TokenStream};
 use quote::ToTokens;
 use syn::{
-	Attribute, Ident,
-	Item::{Enum, Struct, Type, Union},
-	ItemEnum, Meta, MetaNameValue, Token, parse_file, parse_macro_input,
-	punctuated::Punctuated,
+	Item::{self, Enum},
+	LitStr,
+	meta::parser,
+	parse_macro_input,
 };
-use walkdir::WalkDir;
-
-const ENUM_DISPATCH: &'static str = "enum_dispatch";
-
-fn valid_variant(enum_name: &Ident, attrs: Vec<Attribute>) -> bool {
-	for attr in attrs {
-		if let Meta::List(list) = attr.meta {
-			if list.path.to_token_stream().to_string() != "enum_builder_variant" {
-				continue;
-			}
-
-			if list.tokens.to_token_stream().to_string() == enum_name.to_string() {
-				return true;
-			}
-		}
-	}
-
-	false
-}
 
-fn remove_enum_dispatch(mut item: ItemEnum) -> TokenStream {
-	let mut enum_dispatch_index = None;
-
-	for (index, attr) in item.attrs.iter().enumerate() {
-		match &attr.meta {
-			Meta::Path(path) => {
-				if path.to_token_stream().to_string() == ENUM_DISPATCH {
-					enum_dispatch_index = Some(index);
-				}
-			}
-			Meta::List(list) => {
-				if list.path.to_token_stream().to_string() == ENUM_DISPATCH {
-					enum_dispatch_index = Some(index);
-				}
-			}
-			_ => {}
-		}
-	}
-
-	if let Some(index) = enum_dispatch_index {
-		item.attrs.remove(index);
-	}
-
-	item.to_token_stream().into()
-}
+use crate::{decl::Decl, options::Options, variant::VariantArgs};
 
 /// Creates enum variants by discovering types annotated with [macro@enum_builder_variant].
 /// Variants are searched recursively in source files located in the same directory as the macro.
+/// Any variants written in the enum body are kept, followed by the discovered ones in file name order.
+/// The generic parameters and where clauses of the discovered types are added to the enum. Type and
+/// const parameters declared by several types are prefixed with their variant name, e.g. `DogT` and
+/// `FishT`, unless the enum declares them itself to share them.
+///
+/// When the compiler doesn't expose the file of the macro, like in some doc tests or build systems,
+/// the enum's declaration is looked up in the source files under `CARGO_MANIFEST_DIR` instead.
+/// Failing that, compilation fails unless `path_env` names the directory to scan.
 ///
 /// ## Optional Parameters
 /// #### path = [str]
 /// Override the variant scan location with the provided file/directory path.
 ///
+/// #### path_env = [str]
+/// Scan the directory named by the provided environment variable instead of the directory of the
+/// macro, with `path` relative to it, e.g. `path_env = "OUT_DIR"` for sources generated by a build
+/// script. The generated files must be included into the module of the enum with `include!`, and
+/// the files variants are discovered in are tracked, so the enum is rebuilt when they change.
+///
+/// #### scan_all
+/// Also walk into the directories skipped by default: hidden directories, like `.git`, and those
+/// named `target`, `vendor` or `node_modules`, which hold build outputs and sources of other crates.
+/// The scan location itself is always walked.
+///
+/// #### files = str | [str, ...]
+/// Scan exactly the provided files, relative to the scan location, instead of walking it, for a
+/// reviewable set of scanned sources. Variants keep the order of the list.
+///
+/// #### registry_file = [str]
+/// Read variants from the provided registry file, relative to the crate root, in addition to the
+/// discovered ones, for an explicit list of variants reviewed like any other file, or for types
+/// whose sources can't be scanned. Like the lockfile of `lock`, its `[Enum]` sections list
+/// `Variant = "path::to::Type"` entries, whose types are resolved from the module of the enum, and
+/// `#` starts a comment. Combine with `files = []` to only use the registry file.
+///
+/// #### edition = [str]
+/// Parse the scanned sources as written in the provided Rust edition, so 2015 sources can use the
+/// keywords reserved by later editions, like `async` or `try`, as names. Defaults to the edition in
+/// the `[package]` table of the manifest of the crate.
+///
+/// #### cache [= str]
+/// Cache the parts of the scanned sources that scanning looks at across compilations, keyed by
+/// file path and content hash, so unchanged files aren't parsed again. The cache is kept in the
+/// provided directory relative to the crate root, or in `enum_builder/cache` under the target
+/// directory.
+///
+/// #### impls = str
+/// Also discover every type with an `impl Trait for Type` block for the named trait among the
+/// scanned sources, without needing its own [macro@enum_builder_variant] attribute.
+///
+/// #### only_pub
+/// Ignore discovered types that aren't declared `pub`, such as private helpers copied from a
+/// template along with their attribute.
+///
+/// #### include = str | [str, ...]
+/// Only include discovered types whose name matches one of the provided patterns. Patterns match
+/// anywhere in the name, or at its start when prefixed with `^`, or at its end when suffixed with
+/// `$`, so one scan location can feed several enums.
+///
+/// #### exclude = str | [str, ...]
+/// Ignore discovered types whose name matches one of the provided patterns, written like those of
+/// `include`.
+///
+/// #### derive(...)
+/// Derive the provided traits on the generated enum. The derives are always placed after any other
+/// attributes on the enum, so they apply to the final set of variants regardless of attribute order.
+///
+/// #### serde(...)
+/// Derive `serde::Serialize` and `serde::Deserialize` for the generated enum, with the provided
+/// container attributes, such as `tag`, `content` and `rename_all`. Requires `serde` with its
+/// `derive` feature as a dependency of the crate using it.
+///
+/// #### schemars
+/// Derive `schemars::JsonSchema` for the generated enum, following the representation configured
+/// with `serde(...)`. Requires the `schemars` feature, and `schemars` as a dependency of the crate
+/// using it.
+///
+/// #### strum(...)
+/// Derive the provided [strum](https://docs.rs/strum/latest/strum) traits, such as `EnumIter`,
+/// `EnumString` and `IntoStaticStr`, for the generated enum and its `kind` companion. Variants can
+/// carry strum attributes of their own through the `strum(...)` parameter of
+/// [macro@enum_builder_variant]. Requires `strum` with its `derive` feature as a dependency of the
+/// crate using it.
+///
+/// #### vis = [str]
+/// Set the visibility of the generated enum and its helper items, e.g. `"pub(crate)"`. Defaults to
+/// the visibility written on the enum.
+///
+/// #### non_exhaustive
+/// Mark the generated enum `#[non_exhaustive]`, so downstream matches stay forward-compatible as new
+/// variants are discovered.
+///
+/// #### strip_prefix = [str]
+/// Remove the provided prefix from discovered type names when forming variant names.
+///
+/// #### strip_suffix = [str]
+/// Remove the provided suffix from discovered type names when forming variant names, e.g. a
+/// `FooPlugin` type becomes the `Foo` variant with `strip_suffix = "Plugin"`.
+///
+/// #### qualified_paths
+/// Refer to each payload type through the path of its declaring module, relative to the module of
+/// the enum (e.g. `self::plugins::aquatic::Fish`), so the types don't need to be imported. Module
+/// paths are inferred from the standard file layout of Rust modules.
+///
+/// #### reexport
+/// Generate `use` items (with the visibility of the enum) re-exporting every payload type declared
+/// outside the module of the enum, so downstream crates can name them without knowing the internal
+/// module layout.
+///
+/// #### extends = [str]
+/// Include every variant of another enum built with [macro@enum_builder], found under the scan
+/// location, ahead of the discovered variants, and generate a `From` conversion from it. This
+/// allows layering plugin sets, e.g. a core set and an extended set.
+///
+/// #### from = [str]
+/// Build the enum from the variants of another enum built with [macro@enum_builder], found under
+/// the scan location, rather than discovering its own, and generate a `From` conversion into the
+/// other enum and a `TryFrom` conversion from it, handing back values of other variants as the
+/// error. This lets one set of registrations feed several specialized enums.
+///
+/// #### tag = [str]
+/// Only include the variants registered with the provided tag, with the `from = [str]` parameter.
+///
+/// #### group_by_module
+/// Group the variants declared in each child module of the enum's module into an inner enum, held by
+/// a variant named after the module, e.g. `Animal::Aquatic(AquaticAnimal)` where `AquaticAnimal`
+/// contains `Fish` and `Shark`. `From` conversions are generated from every inner enum and grouped
+/// payload type into the outer enum, and from grouped payload types into their inner enum.
+///
+/// #### disambiguate
+/// Variants discovered with the same name are reported as an error, naming both source locations.
+/// With this parameter, colliding variants are instead prefixed with the name of their declaring
+/// module, e.g. `Handler` types in `foo.rs` and `bar.rs` become `FooHandler` and `BarHandler`
+/// holding `foo::Handler` and `bar::Handler` respectively.
+///
+/// #### unit
+/// Generate field-less variants named after the discovered types, instead of variants holding them.
+///
+/// #### repr = [str]
+/// Set the primitive representation of the generated enum, e.g. `"u8"`. Combined with per-variant
+/// `discriminant = N` parameters, this gives variants stable numeric values for FFI or compact
+/// encodings.
+///
+/// #### other [= str]
+/// Append a catch-all variant after the discovered ones. Defaults to a field-less `Other` variant,
+/// or can be set to a custom variant such as `"Unknown(String)"`.
+///
+/// #### default
+/// Generate `impl Default` for the enum, constructing the variant registered with the `default`
+/// parameter from its type's `Default` impl. Exactly one variant must be registered as the default.
+///
+/// #### stable_id
+/// Generate a `stable_id()` method returning a 64-bit hash of the variant name. Unlike positional
+/// discriminants, these identifiers don't shift as variants are added or removed, so they are safe
+/// to persist.
+///
+/// #### kind [= str]
+/// Generate a `Copy`, `Hash`-able companion enum with the same variant names but no fields, named
+/// `<Enum>Kind` unless a name is provided, along with a `kind()` method returning the kind of each
+/// variant. The kind enum shares the `repr` and discriminants of the generated enum, and with
+/// `repr` also gets an `as_<repr>()` method returning its discriminant and a `TryFrom<repr>` impl
+/// mapping discriminants back to kinds, e.g. for wire decoders. Kinds are ordered by the
+/// declaration order of the variants, so they can key a `BTreeMap`, and listed in that order by
+/// the `ALL` constant and `iter()` function of the kind enum.
+///
+/// #### clap
+/// Derive `clap::ValueEnum` for the `kind` companion, with kebab-cased value names, so command line
+/// arguments can name any compiled-in variant. Requires `kind`, the `clap` feature, and `clap` with
+/// its `derive` feature as a dependency of the crate using it.
+///
+/// #### same_kind
+/// Generate a `same_kind()` method comparing only the variants of two values, and an
+/// `<Enum>KindEq` wrapper implementing `Eq` and `Hash` the same way, so values can be grouped by
+/// variant even when their fields don't implement `Eq`.
+///
+/// #### from_str
+/// Generate `from_name()` and a `FromStr` impl for the `kind` companion, mapping variant names to
+/// kinds. Requires `kind`, and can't be combined with deriving strum's `EnumString`, which also
+/// implements `FromStr`.
+///
+/// #### case_insensitive
+/// Match variant names case-insensitively when parsing kinds. Requires `from_str`.
+///
+/// #### rename_all = [str]
+/// Rename variants following the provided case convention, one of `snake_case`, `kebab-case` and
+/// `SCREAMING_SNAKE_CASE`, in the names written by `display`, parsed by `from_str`, recorded by
+/// `manifest`, and used as serde tags, e.g. `SeaLion` becomes `sea-lion` with `kebab-case`. Serde
+/// tags are set with a `rename` attribute per variant, keeping those written on declared variants,
+/// so they can't be combined with a serde `rename_all` attribute on the enum.
+///
+/// #### accessors
+/// Generate an `is_*()` predicate for every variant, named after the variant in `snake_case`, and
+/// `as_*()`, `as_*_mut()` and `into_*()` accessors for every variant holding a single payload.
+///
+/// #### from
+/// Implement `From` for the enum from every discovered type, for crates that don't use
+/// [enum_dispatch](https://docs.rs/enum_dispatch/latest/enum_dispatch), which already does. Can't
+/// be combined with `unit` or enum_dispatch.
+///
+/// #### try_from
+/// Implement `TryFrom<Enum>` for every discovered type, returning the enum unchanged when it holds
+/// another variant. Can't be combined with `unit`.
+///
+/// #### for_each_variant
+/// Generate a crate-visible `<enum>_for_each_variant!` macro, named after the enum in `snake_case`,
+/// which invokes the provided macro once for every discovered variant with the variant name, the
+/// payload type and any extra arguments. Payload types declared in the enum's module or named
+/// through `self` and `super` paths are named from the crate root, so the macro can be invoked
+/// from any module, while other payload types must be in scope where the macro is used. The enum's
+/// module is inferred from the standard file layout of Rust modules, which doesn't account for
+/// inline modules.
+///
+/// #### variant_macro = [str]
+/// Invoke the macro at the provided path next to the enum once for every discovered variant, as
+/// `hook!(Enum, Variant, Payload, { key = "value", ... });` with the `meta` entries of the variant,
+/// letting other crates generate their own items, like metrics or bindings, in lockstep with the
+/// enum. The macro must be in scope at the enum, e.g. declared with `macro_rules!` before it.
+///
+/// #### match_variant
+/// Generate a crate-visible `match_<enum>!(value, inner => expr)` macro, named after the enum in
+/// `snake_case`, which matches `value` against every variant holding a single payload and evaluates
+/// `expr` with the payload bound to `inner`. Enums with other variants need a trailing
+/// `_ => fallback` arm. The enum is named from the crate root, like the payload types of
+/// `for_each_variant`, so the macro can be invoked from any module.
+///
+/// #### assert_variants
+/// Generate a crate-visible `assert_<enum>_variants!(...)` macro, named after the enum in
+/// `snake_case`, which fails to compile unless it's passed exactly the names of the discovered
+/// variants, in any order. Invoking it next to the enum gives reviewers an explicit checkpoint that
+/// breaks the build whenever a variant is added or removed.
+///
+/// #### visitor
+/// Generate an `<Enum>Visitor` trait with a `visit_*()` method for every variant taking references
+/// to its fields, and an `accept()` method calling the method of the current variant. Adding a
+/// variant then fails to compile until every visitor handles it.
+///
+/// #### as_ref
+/// Generate an `<Enum>Ref<'a>` companion enum holding references to the fields of each variant,
+/// and an `as_ref()` method borrowing the enum as it, for passing cheap views of values around.
+///
+/// #### as_mut
+/// Generate an `<Enum>Mut<'a>` companion enum holding mutable references to the fields of each
+/// variant, and an `as_mut()` method borrowing the enum as it, for mutating values in place.
+///
+/// #### all_default
+/// Generate an `all_default()` method returning an iterator over every variant, each constructed
+/// from the `Default` impls of its fields. Types lacking `Default` are reported by name.
+///
+/// #### arbitrary
+/// Implement `arbitrary::Arbitrary` for the enum, picking a variant from the fuzzer input and
+/// building its fields from their own `Arbitrary` impls, so fuzz targets can take the enum directly.
+/// Requires the `arbitrary` feature, and `arbitrary` as a dependency of the crate using it. Can't be
+/// combined with `dyn_fallback`.
+///
+/// #### proptest
+/// Generate an `arbitrary_<enum>()` function returning a proptest `Strategy` picking any variant,
+/// with fields generated from their own `Arbitrary` impls, so property tests cover every discovered
+/// variant. Requires the `proptest` feature, and `proptest` as a dependency of the crate using it.
+/// Not supported for generic enums, and can't be combined with `dyn_fallback`.
+///
+/// #### dispatch = str | [str, ...]
+/// Implement the named traits for the enum, found among the scanned sources, by forwarding every
+/// method to the type held by the current variant. This works like
+/// [enum_dispatch](https://docs.rs/enum_dispatch/latest/enum_dispatch) without depending on the
+/// order of macros. Every method must take `self`, and every variant must hold a single type.
+/// `async fn` methods await the method of the variant, and the `#[async_trait]` attribute of traits
+/// using [async_trait](https://docs.rs/async-trait/latest/async_trait) is copied to their impls.
+/// For traits that can be made into trait objects, `as_dyn()` and `as_dyn_mut()` methods borrow the
+/// held type as `&dyn Trait` and `&mut dyn Trait`, named `as_dyn_<trait>()` and
+/// `as_dyn_<trait>_mut()` when several traits are dispatched.
+///
+/// #### dyn_fallback = str
+/// Append a `Dynamic(Box<dyn Trait>)` variant for the named trait, found among the scanned sources,
+/// so types only known at runtime can be held alongside the discovered ones. When the trait is also
+/// dispatched, its methods are forwarded to the boxed value, so they must take `&self` or
+/// `&mut self`. Can't be combined with `unit`.
+///
+/// #### registry
+/// Generate a static `<ENUM>_REGISTRY` slice of `<Enum>VariantDescriptor`s, describing every
+/// discovered variant with its name, the path of the module declaring its type, its tags, its
+/// priority, and a constructor calling the `constructor` the type was registered with, or its
+/// `Default` impl. Descriptors are ordered by decreasing priority, then in declaration order. Not
+/// supported for generic enums.
+///
+/// #### from_name
+/// Generate a `from_name()` function constructing the discovered variant with the provided name
+/// through the registry, for loading plugins from configuration. Requires `registry`.
+///
+/// #### config = [str]
+/// Pass a reference to the provided configuration type to registry constructors, and to
+/// `from_name()`, so `constructor` functions can initialize plugins from it. Requires `registry`.
+///
+/// #### serde_factory
+/// Generate a `from_tagged_value()` function deserializing a `serde_json::Value` as the type of the
+/// discovered variant named by a tag, returning an `<Enum>FactoryError` for unknown tags and
+/// invalid values. Requires the `serde_factory` feature, and `serde_json` as a dependency of the
+/// crate using it. Can't be combined with `unit`.
+///
+/// #### display
+/// Generate a `Display` impl writing the name of each variant, for the enum and the `kind`
+/// companion if enabled.
+///
+/// #### debug_impl = [str]
+/// Set to `"verbose"` to generate a `Debug` impl printing the enum and variant names and the full
+/// path of the payload type before the payload's own `Debug` output, e.g.
+/// `Animal::Dog (plugins::canine::Dog) Dog { age: 3 }`, telling apart variants discovered across
+/// many modules. Variants written in the enum body print their fields like a derived impl. Can't be
+/// combined with `dyn_fallback` or a derived `Debug`.
+///
+/// #### error
+/// Build an enum aggregating error types: generate `Display` and `Error` impls forwarding to the
+/// error held by each variant, including its `source()`, and `From` conversions from every
+/// discovered type, as a scan-driven alternative to hand-maintained thiserror enums. `Error` also
+/// requires the enum to implement `Debug`, e.g. with `derive(Debug)`. Every variant must hold a
+/// single error. Can't be combined with `display`, `unit`, `group_by_module` or enum_dispatch.
+///
+/// #### names
+/// Generate a `VARIANT_NAMES` constant listing the name of every variant, and a `name()` method
+/// returning the name of each variant. Both are also generated for the `kind` companion if enabled.
+///
+/// #### index
+/// Generate a `COUNT` constant holding the number of variants, and a `variant_index()` method
+/// returning the position of each variant, for sizing and indexing per-variant tables. Both are also
+/// generated for the `kind` companion if enabled.
+///
+/// #### module_path
+/// Generate a `module_path()` method returning the path of the module the payload of each variant
+/// is declared in, like the `module_path` of the `registry` descriptors, so plugin hosts can report
+/// where an implementation came from. Variants declared on the enum report the module of the enum.
+///
+/// #### source_location
+/// Generate a `source_location()` method returning the file, relative to the crate root, and the
+/// line the payload of each variant is declared at, for pointing runtime diagnostics at the source
+/// of a misbehaving plugin. Variants declared on the enum report the location of the enum.
+///
+/// #### type_name
+/// Generate a `type_name()` method returning the full name of the type held by each variant, from
+/// `std::any::type_name`, for logging dynamically selected variants. Variants not holding a single
+/// value report the name of the enum.
+///
+/// #### type_ids
+/// Generate a `type_ids()` function returning the `TypeId` of every payload type along with its
+/// kind, a `kind_of::<T>()` function returning the kind of the variant holding `T`, and a
+/// `holds::<T>()` method, so generic host code can work with concrete plugin types. Requires
+/// `kind`. Not supported for generic enums.
+///
+/// #### tags
+/// Generate a `tags()` method returning the tags each variant was registered with through
+/// `tags(...)` on [macro@enum_builder_variant], so hosts can filter plugins by capability.
+///
+/// #### variant_sizes [= int]
+/// Generate a `VARIANT_SIZES` constant listing the name of every variant along with the size of its
+/// fields, for deciding which payloads to box. With a ratio, a deprecation warning is emitted when
+/// the largest variant is more than that many times larger than the next largest one. The ratio
+/// isn't supported for generic enums.
+///
+/// #### metadata
+/// Generate a `metadata()` accessor returning the key/value pairs each variant declared with
+/// `meta(...)`, along with a static `<ENUM>_METADATA` table covering every variant.
+///
+/// #### lock = [str]
+/// Check the discovered variants, along with the files declaring them, against those recorded for
+/// the enum in the provided lockfile relative to the crate root, failing to compile on any
+/// difference. Committing the lockfile makes every added or removed variant show up in review,
+/// rather than being picked up silently from a stray annotated file. Set the
+/// `ENUM_BUILDER_UPDATE_LOCK=1` environment variable to record the discovered variants instead.
+///
+/// #### pregenerated = [str]
+/// Write the generated code, formatted with rustfmt when it's available, to the provided file
+/// relative to the crate root and include it from there, so IDEs and readers see the enum as plain
+/// source. The file is meant to be committed; compilation fails with a regeneration hint whenever it
+/// no longer matches the expansion. Set the `ENUM_BUILDER_UPDATE_PREGENERATED=1` environment
+/// variable to rewrite it instead. Lints like `dead_code` then apply to the generated items as they
+/// would to hand-written ones.
+///
+/// #### manifest [= str]
+/// Write a JSON manifest of the discovered variants, with their names, types, source locations and
+/// modules relative to the module of the enum, for external tools. It's written to the provided
+/// path relative to the crate root, or to `$OUT_DIR/enum_builder/<Enum>.json` when the crate has a
+/// build script.
+///
+/// #### mod_name = [str]
+/// Declare the enum and every item generated for it in a module with the provided name, with the
+/// visibility of the enum, and re-export the enum itself next to it. Generated helper items are
+/// then referred to through that module, e.g. `animal_gen::AnimalKind`, keeping them out of the
+/// namespace of the enum's module.
+///
+/// #### no_std
+/// Refer to the `Box` of the `dyn_fallback` parameter and the `String` of the `serde_factory`
+/// parameter through the `alloc` crate, which `#![no_std]` crates must then declare with
+/// `extern crate alloc;`. All other generated code only uses `core`, so it works in `#![no_std]`
+/// crates either way.
+///
+/// #### helpers_doc = [str]
+/// Set to `"hidden"` to mark every generated item other than the enum itself, like the kind enum,
+/// the registry or the accessor methods, with `#[doc(hidden)]`, keeping rustdoc focused on the enum
+/// when many companions are enabled. The items stay usable. Defaults to `"visible"`.
+///
+/// #### debug
+/// Write the generated code, formatted with rustfmt when it's available, to
+/// `$OUT_DIR/enum_builder/<Enum>.rs` when the crate has a build script, or to stderr otherwise.
+///
+/// #### stats [= str]
+/// Report how many files were walked, skipped without parsing, loaded from the cache and parsed,
+/// how many directories were excluded, how many discovered types were dropped by each of
+/// `only_pub`, `include` and `exclude`, and how long scanning took, covering every scan made for
+/// the enum. The report is written to the provided path relative to the crate root, or to
+/// `$OUT_DIR/enum_builder/<Enum>.stats` when the crate has a build script, or to stderr otherwise.
+///
+/// #### allow_empty [= bool]
+/// Build the enum without any variants, along with its derives, attributes and helpers, when none
+/// are declared or discovered. By default this is reported as an error naming the scanned location,
+/// since it usually means the `path` is wrong.
+///
+/// #### expect_count = [int]
+/// Fail to compile, listing the discovered variants, unless exactly the provided number of variants
+/// is discovered, e.g. to catch a mistyped path dropping plugins. Variants written in the enum body
+/// aren't counted.
+///
+/// #### strict_targets
+/// Fail to compile when a type in the scanned sources is registered for an enum that no
+/// enum_builder enum in the crate builds, like a mistyped `#[enum_builder_variant(Aminal)]`. The
+/// crate is only scanned for the enums it builds when such registrations are found.
+///
+/// #### warn_targets
+/// Like `strict_targets`, but only trigger a deprecation warning naming each registration for an
+/// unknown enum.
+///
+/// #### must_impl = str
+/// Statically assert that every discovered payload type implements the provided bounds, e.g.
+/// `"AnimalSound + Send + Sync"`, so a missing impl fails with an error naming the offending type
+/// rather than deep inside generated dispatch code.
+///
+/// #### assert_send_sync
+/// Statically assert that every discovered payload type is `Send` and `Sync`, failing with an error
+/// naming the offending type, for enums shared across threads. Shorthand for including
+/// `Send + Sync` in `must_impl`.
+///
+/// #### inventory
+/// Generate an `<Enum>Registration` type collected with
+/// [inventory](https://docs.rs/inventory/latest/inventory), and an `<Enum>Registration::iter()`
+/// function iterating at runtime over the types submitted with the `inventory` parameter of
+/// [macro@enum_builder_variant], including those declared in other crates that scanning can't
+/// reach. Requires the `inventory` feature, and `inventory` as a dependency of the crate using it.
+///
 /// ## Examples:
 /// ```
 /// #[enum_builder]
 /// enum Animal {}
 /// ```
 /// ```
+/// #[enum_builder]
+/// enum Event {
+///     Tick,
+///     Shutdown,
+/// }
+/// ```
+/// ```
 /// #[enum_builder(path = "animals/")]
 /// enum Animal {}
 /// ```
@@ -119,6 +552,479 @@ fn remove_enum_dispatch(mut item: ItemEnum) -> TokenStream {
 /// #[enum_builder(path = "animals.rs")]
 /// enum Animal {}
 /// ```
+/// ```
+/// #[enum_builder(path_env = "OUT_DIR", path = "plugins/")]
+/// enum Plugin {}
+///
+/// include!(concat!(env!("OUT_DIR"), "/plugins/codecs.rs"));
+/// ```
+/// ```
+/// #[enum_builder(files = ["dog.rs", "aquatic/fish.rs"])]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(path = "legacy/", edition = "2015")]
+/// enum Handler {}
+/// ```
+/// ```
+/// #[enum_builder(path = "plugins/", cache)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(impls = "AnimalSound", dispatch = "AnimalSound")]
+/// enum Animal {}
+///
+/// struct Dog;
+///
+/// impl AnimalSound for Dog {
+///     fn sound(&self) -> String {
+///         "woof".to_owned()
+///     }
+/// }
+/// ```
+/// ```
+/// #[enum_builder(only_pub)]
+/// pub enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(include = "Backend$", exclude = ["^Mock", "^Fake"])]
+/// enum Backend {}
+/// ```
+/// ```
+/// #[enum_builder(mod_name = "animal_gen", kind, registry)]
+/// pub enum Animal {}
+///
+/// let kind = animal_gen::AnimalKind::Dog;
+/// ```
+/// ```
+/// #![no_std]
+///
+/// extern crate alloc;
+///
+/// #[enum_builder(no_std, dispatch = "Driver", dyn_fallback = "Driver")]
+/// enum Device {}
+/// ```
+/// ```
+/// #[enum_builder(derive(Debug, Clone, PartialEq))]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(serde(tag = "type", content = "data", rename_all = "snake_case"))]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(serde(tag = "type"), schemars)]
+/// enum Animal {}
+///
+/// let schema = schemars::schema_for!(Animal);
+/// ```
+/// ```
+/// #[enum_builder(kind, strum(IntoStaticStr, EnumString))]
+/// enum Animal {}
+///
+/// #[enum_builder_variant(Animal, strum(serialize = "doggo"))]
+/// struct Dog {}
+/// ```
+/// ```
+/// #[enum_builder(vis = "pub(crate)")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(non_exhaustive)]
+/// pub enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(strip_suffix = "Plugin")]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(qualified_paths)]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(reexport)]
+/// pub enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(extends = "CoreAnimal")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(from = "Animal", tag = "aquatic")]
+/// enum AquaticAnimal {}
+/// ```
+/// ```
+/// #[enum_builder(group_by_module, qualified_paths)]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(disambiguate)]
+/// enum Handler {}
+/// ```
+/// ```
+/// #[enum_builder(unit, repr = "u8")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(other = "Unknown(String)")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(default)]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(stable_id)]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(kind)]
+/// enum Animal {}
+///
+/// let kinds = HashSet::from([AnimalKind::Dog, AnimalKind::Cow]);
+/// ```
+/// ```
+/// #[enum_builder(kind, clap)]
+/// enum Animal {}
+///
+/// #[derive(clap::Parser)]
+/// struct Args {
+///     #[arg(long)]
+///     animal: AnimalKind,
+/// }
+/// ```
+/// ```
+/// #[enum_builder(same_kind)]
+/// enum Animal {}
+///
+/// let mut counts: HashMap<AnimalKindEq, usize> = HashMap::new();
+///
+/// for animal in animals {
+///     *counts.entry(AnimalKindEq(animal)).or_default() += 1;
+/// }
+/// ```
+/// ```
+/// #[enum_builder(kind, from_str, case_insensitive)]
+/// enum Animal {}
+///
+/// let kind: AnimalKind = "dog".parse()?;
+/// ```
+/// ```
+/// #[enum_builder(kind, from_str, display, rename_all = "kebab-case")]
+/// enum Animal {}
+///
+/// assert_eq!("sea-lion".parse::<AnimalKind>()?.to_string(), "sea-lion");
+/// ```
+/// ```
+/// #[enum_builder(accessors)]
+/// enum Animal {}
+///
+/// if animal.is_dog() {
+///     println!("woof");
+/// }
+///
+/// let dog: Option<&Dog> = animal.as_dog();
+/// let dog: Result<Dog, Animal> = animal.into_dog();
+/// ```
+/// ```
+/// #[enum_builder(from)]
+/// enum Animal {}
+///
+/// let animal: Animal = Dog {}.into();
+/// ```
+/// ```
+/// #[enum_builder(try_from)]
+/// enum Animal {}
+///
+/// let dog = Dog::try_from(animal)?;
+/// ```
+/// ```
+/// #[enum_builder(for_each_variant)]
+/// enum Animal {}
+///
+/// macro_rules! register {
+///     ($variant:ident, $payload:ty, $host:expr) => {
+///         $host.register::<$payload>(stringify!($variant));
+///     };
+/// }
+///
+/// animal_for_each_variant!(register, host);
+/// ```
+/// ```
+/// macro_rules! describe {
+///     ($enum:ident, $variant:ident, $payload:ty, { $($key:ident = $value:literal),* }) => {
+///         impl Described for $payload {
+///             const KEYS: &[&str] = &[$(stringify!($key)),*];
+///         }
+///     };
+/// }
+///
+/// #[enum_builder(variant_macro = "describe")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(match_variant)]
+/// enum Animal {}
+///
+/// let size = match_animal!(&animal, inner => std::mem::size_of_val(inner));
+/// ```
+/// ```
+/// #[enum_builder(assert_variants)]
+/// enum Animal {}
+///
+/// assert_animal_variants!(Dog, Cow, Fish);
+/// ```
+/// ```
+/// #[enum_builder(visitor)]
+/// enum Animal {}
+///
+/// struct Feeder;
+///
+/// impl AnimalVisitor for Feeder {
+///     fn visit_dog(&mut self, dog: &Dog) {}
+///     fn visit_cow(&mut self, cow: &Cow) {}
+///     fn visit_fish(&mut self, fish: &Fish) {}
+/// }
+///
+/// animal.accept(&mut Feeder);
+/// ```
+/// ```
+/// #[enum_builder(as_ref)]
+/// enum Animal {}
+///
+/// fn feed(animal: AnimalRef<'_>) {}
+///
+/// feed(animal.as_ref());
+/// ```
+/// ```
+/// #[enum_builder(as_mut)]
+/// enum Animal {}
+///
+/// if let AnimalMut::Dog(dog) = animal.as_mut() {
+///     dog.walks += 1;
+/// }
+/// ```
+/// ```
+/// #[enum_builder(all_default)]
+/// enum Animal {}
+///
+/// for animal in Animal::all_default() {
+///     host.register(animal);
+/// }
+/// ```
+/// ```
+/// #[enum_builder(arbitrary)]
+/// enum Animal {}
+///
+/// fuzz_target!(|animal: Animal| host.dispatch(animal));
+/// ```
+/// ```
+/// #[enum_builder(proptest)]
+/// enum Animal {}
+///
+/// proptest!(|(animal in arbitrary_animal())| host.dispatch(animal));
+/// ```
+/// ```
+/// trait AnimalSound {
+///     fn sound(&self) -> String;
+/// }
+///
+/// #[enum_builder(dispatch = "AnimalSound")]
+/// enum Animal {}
+///
+/// println!("{}", animal.sound());
+///
+/// let sounds: Vec<&dyn AnimalSound> = animals.iter().map(Animal::as_dyn).collect();
+/// ```
+/// ```
+/// #[enum_builder(dispatch = ["AnimalSound", "AnimalDiet"])]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(dispatch = "AnimalSound", dyn_fallback = "AnimalSound")]
+/// enum Animal {}
+///
+/// let animal = Animal::Dynamic(plugin.load_animal());
+///
+/// println!("{}", animal.sound());
+/// ```
+/// ```
+/// #[enum_builder(registry)]
+/// enum Animal {}
+///
+/// for descriptor in ANIMAL_REGISTRY {
+///     if let Some(constructor) = descriptor.constructor {
+///         host.register(descriptor.name, constructor());
+///     }
+/// }
+/// ```
+/// ```
+/// // animals.toml:
+/// // [Animal]
+/// // Dog = "crate::zoo::Dog"
+/// // Parrot = "::birds::Parrot"
+///
+/// #[enum_builder(registry_file = "animals.toml", files = [])]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(registry, from_name)]
+/// enum Animal {}
+///
+/// let animal = Animal::from_name(&config.animal).expect("unknown animal");
+/// ```
+/// ```
+/// #[enum_builder(registry, from_name, config = "ZooConfig")]
+/// enum Animal {}
+///
+/// let animal = Animal::from_name(&config.animal, &config).expect("unknown animal");
+/// ```
+/// ```
+/// #[enum_builder(serde_factory)]
+/// enum Animal {}
+///
+/// let animal = Animal::from_tagged_value(&config.kind, config.settings)?;
+/// ```
+/// ```
+/// #[enum_builder(display)]
+/// enum Animal {}
+///
+/// println!("loaded plugin {}", Animal::from(Dog {}));
+/// ```
+/// ```
+/// #[enum_builder(debug_impl = "verbose")]
+/// enum Animal {}
+///
+/// eprintln!("unexpected animal {:?}", animal);
+/// ```
+/// ```
+/// #[enum_builder(error, derive(Debug))]
+/// pub enum AppError {}
+///
+/// fn load(path: &Path) -> Result<Config, AppError> {
+///     let text = fs::read_to_string(path).map_err(ReadError)?;
+///
+///     Ok(parse(&text)?)
+/// }
+/// ```
+/// ```
+/// #[enum_builder(names)]
+/// enum Animal {}
+///
+/// println!("{} of {:?}", Animal::from(Dog {}).name(), Animal::VARIANT_NAMES);
+/// ```
+/// ```
+/// #[enum_builder(index)]
+/// enum Animal {}
+///
+/// let mut counts = [0; Animal::COUNT];
+///
+/// counts[Animal::from(Dog {}).variant_index()] += 1;
+/// ```
+/// ```
+/// #[enum_builder(names, module_path)]
+/// enum Animal {}
+///
+/// println!("loaded {} from {}", animal.name(), animal.module_path());
+/// ```
+/// ```
+/// #[enum_builder(source_location)]
+/// enum Animal {}
+///
+/// let (file, line) = animal.source_location();
+///
+/// eprintln!("plugin declared at {}:{} failed", file, line);
+/// ```
+/// ```
+/// #[enum_builder(type_name)]
+/// enum Animal {}
+///
+/// println!("selected {}", animal.type_name());
+/// ```
+/// ```
+/// #[enum_builder(kind, type_ids)]
+/// enum Animal {}
+///
+/// fn adopt<T: 'static>(animal: Animal) -> Option<Animal> {
+///     animal.holds::<T>().then_some(animal)
+/// }
+/// ```
+/// ```
+/// #[enum_builder(tags)]
+/// enum Animal {}
+///
+/// let swimmers = animals.iter().filter(|animal| animal.tags().contains(&"aquatic"));
+/// ```
+/// ```
+/// #[enum_builder(variant_sizes = 4)]
+/// enum Animal {}
+///
+/// for (name, size) in Animal::VARIANT_SIZES {
+///     println!("{}: {} bytes", name, size);
+/// }
+/// ```
+/// ```
+/// #[enum_builder(metadata)]
+/// enum Animal {}
+///
+/// let author = Animal::from(Dog {}).metadata().get("author");
+/// ```
+/// ```
+/// #[enum_builder(lock = "enum_builder.lock")]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(pregenerated = "src/generated/animal.rs")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(manifest = "target/animals.json")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(kind, registry, accessors, helpers_doc = "hidden")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(debug)]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(path = "plugins/", exclude = "^Mock", stats)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(path = "plugins/", allow_empty)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(expect_count = 12)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(strict_targets)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(warn_targets)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(must_impl = "AnimalSound + Send + Sync")]
+/// enum Animal {}
+/// ```
+/// ```
+/// #[enum_builder(assert_send_sync)]
+/// enum Plugin {}
+/// ```
+/// ```
+/// #[enum_builder(inventory)]
+/// pub enum Animal {}
+///
+/// for registration in AnimalRegistration::iter() {
+///     println!("{} from {}", registration.type_name, registration.module_path);
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn enum_builder(attrs: TokenStream, item: TokenStream) -> TokenStream {
 	let parsed_item = parse_macro_input!(item);
@@ -127,110 +1033,121 @@ pub fn enum_builder(attrs: TokenStream, item: TokenStream) -> TokenStream {
 		return parsed_item.to_token_stream().into();
 	};
 
-	let Some(dir) = Span::call_site().local_file() else {
-		return remove_enum_dispatch(item_enum);
-	};
-
-	let mut dir = dir.parent().unwrap().to_owned();
-	let mut enum_variants: Vec<String> = vec![];
-	let attrs =
-		parse_macro_input!(attrs with Punctuated::<MetaNameValue, Token![,]>::parse_terminated);
-
-	for attr in attrs {
-		let name = attr.path.to_token_stream().to_string();
+	let mut options = Options::default();
+	let options_parser = parser(|meta| options.parse(meta));
 
-		if name != "path" {
-			continue;
-		}
+	parse_macro_input!(attrs with options_parser);
 
-		dir = dir.join(
-			attr.value
-				.to_token_stream()
-				.to_string()
-				.trim_matches('"')
-				.to_owned(),
-		);
-	}
-
-	for entry in WalkDir::new(dir) {
-		let Ok(entry) = entry else { continue };
-		let path = entry.path();
-
-		if path.is_dir() {
-			continue;
-		}
-
-		if path.extension() != Some(OsStr::new("rs")) {
-			continue;
-		};
-
-		let src = fs::read_to_string(path)
-			.expect(format!("unable to read file {}", path.to_string_lossy()).as_str());
-		let syntax = parse_file(&src)
-			.expect(format!("unable to parse file {}", path.to_string_lossy()).as_str());
-
-		for item in syntax.items {
-			let ident;
-			let generics;
-
-			match item {
-				Struct(item) => {
-					if !valid_variant(&item_enum.ident, item.attrs) {
-						continue;
-					}
-
-					ident = item.ident;
-					generics = item.generics.to_token_stream().to_string();
-				}
-				Type(item) => {
-					if !valid_variant(&item_enum.ident, item.attrs) {
-						continue;
-					}
-
-					ident = item.ident;
-					generics = item.generics.to_token_stream().to_string();
-				}
-				Enum(item) => {
-					if !valid_variant(&item_enum.ident, item.attrs) {
-						continue;
-					}
-
-					ident = item.ident;
-					generics = item.generics.to_token_stream().to_string();
-				}
-				Union(item) => {
-					if !valid_variant(&item_enum.ident, item.attrs) {
-						continue;
-					}
-
-					ident = item.ident;
-					generics = item.generics.to_token_stream().to_string();
-				}
-				_ => continue,
-			}
-
-			enum_variants.push(format!("{}({}{})", ident, ident, generics));
-		}
-	}
+	expand::call_site_file(&options, &item_enum.ident, Span::call_site().local_file())
+		.and_then(|file| expand::enum_builder(options, item_enum, &file))
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
 
-	if enum_variants.is_empty() {
-		return remove_enum_dispatch(item_enum);
-	}
+/// Function-like form of [macro@enum_builder], for contexts where attribute macros are awkward,
+/// such as inside other macros or generated modules. The enum is declared with an optional body,
+/// followed by the parameters of [macro@enum_builder], each terminated by a semicolon.
+///
+/// Enums declared this way are found by the `extends` parameter and [macro@enum_builder_check]
+/// like those built with the attribute.
+///
+/// ## Examples
+/// ```
+/// enum_builder_decl! {
+///     #[derive(Debug)]
+///     pub enum Animal;
+///     path = "animals/";
+/// }
+/// ```
+/// ```
+/// enum_builder_decl! {
+///     pub enum Event {
+///         Tick,
+///         Shutdown,
+///     }
+///     kind;
+///     derive(Clone, PartialEq);
+/// }
+/// ```
+#[proc_macro]
+pub fn enum_builder_decl(input: TokenStream) -> TokenStream {
+	let Decl { item, options } = parse_macro_input!(input);
 
-	format!(
-		"#[enum_dispatch]\nenum {}<'a> {{ {} }}",
-		item_enum.ident,
-		enum_variants.join(",\n")
-	)
-	.parse()
-	.unwrap()
+	expand::call_site_file(&options, &item.ident, Span::call_site().local_file())
+		.and_then(|file| expand::enum_builder(options, item, &file))
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
 }
 
 /// Creates a variant for the provided enum type.
 ///
+/// Applies to structs, enums, unions and type aliases, and to impl blocks, registering the type
+/// they're for when it's declared where it can't be annotated. That type must be named by a single
+/// identifier or an absolute path, and is skipped by the `only_pub` parameter of
+/// [macro@enum_builder] as its visibility isn't known.
+///
 /// ## Required Parameters
-/// #### enum
-/// Sets the enum type the variant is registered for.
+/// #### enum | enum = [str]
+/// Sets the enum type the variant is registered for, either positionally as the first parameter or
+/// by name anywhere among the parameters, in which case a bare first parameter is a flag like
+/// `default`.
+///
+/// ## Optional Parameters
+/// #### name = [str]
+/// Overrides the name of the generated variant, which defaults to the name of the type, after the
+/// `strip_prefix` and `strip_suffix` parameters of [macro@enum_builder] apply.
+///
+/// #### meta(key = [str], ...)
+/// Attaches descriptive key/value pairs to the variant, exposed at runtime when the enum is built
+/// with the `metadata` parameter.
+///
+/// #### default
+/// Makes the generated `Default` impl of the enum construct this variant.
+///
+/// #### discriminant = [i64]
+/// Sets the explicit discriminant of the generated variant. Requires the enum to be built with the
+/// `repr` or `unit` parameter.
+///
+/// #### id = [u32]
+/// Sets the wire id of the generated variant, returned by a generated `wire_id()` method that keeps
+/// working as variants are renamed, along with `wire_id()` and `from_wire_id()` on the kind enum.
+/// Once a variant has an id every variant of the enum needs one, and ids must be unique.
+///
+/// #### priority = [i32]
+/// Sets the priority of the generated variant, zero by default. The registry generated by the
+/// `registry` parameter of [macro@enum_builder] lists variants by decreasing priority, and once a
+/// variant has a priority, enums with a kind enum get a `kinds_by_priority()` function returning
+/// every kind in that order. Variants with the same priority stay in declaration order.
+///
+/// #### constructor = [str]
+/// Names the function the registry generated by the `registry` parameter of [macro@enum_builder]
+/// constructs the type with, instead of its `Default` impl. It takes no arguments, or a reference to
+/// the configuration type named by the `config` parameter.
+///
+/// #### store = [str]
+/// Makes the generated variant hold the provided type instead of the annotated one, e.g. a compact
+/// or owned form of a type that's convenient to write but too large or borrowed to store. The type
+/// is resolved from the module of the annotated type unless its path is absolute. With the `from`
+/// parameter of [macro@enum_builder], a `From` impl for the annotated type converts it through the
+/// `From` impl of the stored type. The generics of the annotated type aren't added to the enum.
+///
+/// #### strum(...)
+/// Attaches the provided strum attributes to the generated variant and its `kind` counterpart, when
+/// the enum is built with the `strum` parameter.
+///
+/// #### tags(...)
+/// Tags the variant, selecting it for enums built with the `from = [str]` and `tag = [str]`
+/// parameters of [macro@enum_builder].
+///
+/// #### deprecated = [str]
+/// Marks the generated variant `#[deprecated]` with the provided note, so code constructing or
+/// matching it gets the standard deprecation warning. Items generated for the enum itself don't
+/// warn.
+///
+/// #### inventory
+/// Also submits the type to the `<Enum>Registration` type generated by the `inventory` parameter of
+/// [macro@enum_builder], which must be in scope, so it can be found at runtime from any crate.
+/// Requires the `inventory` feature.
 ///
 /// ## Examples
 /// ```
@@ -244,19 +1161,176 @@ pub fn enum_builder(attrs: TokenStream, item: TokenStream) -> TokenStream {
 /// ```
 /// #[enum_builder_variant(Animal)]
 /// enum Dog {
-/// 	Kelpy,
-/// 	BorderCollie,
-/// 	Terrier,
+///     Kelpy,
+///     BorderCollie,
+///     Terrier,
 /// }
 /// ```
 /// ```
 /// #[enum_builder_variant(Animal)]
 /// union BarnAnimal {
-///		Horse: u32,
-///		Donkey: f32,
-///	}
+///     Horse: u32,
+///     Donkey: f32,
+/// }
+/// ```
+/// ```
+/// use zoo::Lion;
+///
+/// #[enum_builder_variant(Animal)]
+/// impl AnimalSound for Lion {
+///     fn sound(&self) -> &'static str {
+///         "roar"
+///     }
+/// }
+/// ```
+/// ```
+/// #[enum_builder_variant(enum = "Animal", name = "Doggo")]
+/// struct Dog {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, default)]
+/// #[derive(Default)]
+/// struct Cat {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, discriminant = 3)]
+/// struct Horse {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, id = 42)]
+/// struct Horse {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, priority = 5)]
+/// struct Dog {}
+///
+/// let first = Animal::kinds_by_priority()[0];
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, constructor = "Dog::from_config")]
+/// struct Dog {}
+///
+/// impl Dog {
+///     fn from_config(config: &ZooConfig) -> Self {
+///         Dog {}
+///     }
+/// }
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, store = "CompactDog")]
+/// struct Dog<'a> {
+///     name: &'a str,
+/// }
+///
+/// impl<'a> From<Dog<'a>> for CompactDog {
+///     fn from(dog: Dog<'a>) -> Self {
+///         CompactDog::new(dog.name)
+///     }
+/// }
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, meta(author = "me", version = "1.2"))]
+/// struct Cat {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, strum(serialize = "doggo"))]
+/// struct Dog {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, tags("aquatic"))]
+/// struct Fish {}
+/// ```
+/// ```
+/// #[enum_builder_variant(Animal, deprecated = "use Cat instead")]
+/// struct Kitten {}
+/// ```
+/// ```
+/// use animals::AnimalRegistration;
+///
+/// #[enum_builder_variant(Animal, inventory)]
+/// pub struct Dog {}
 /// ```
 #[proc_macro_attribute]
-pub fn enum_builder_variant(_: TokenStream, item: TokenStream) -> TokenStream {
-	item
+pub fn enum_builder_variant(attrs: TokenStream, item: TokenStream) -> TokenStream {
+	let args = match VariantArgs::parse(attrs.into()) {
+		Ok(args) => args,
+		Err(err) => {
+			let mut tokens = err.into_compile_error();
+
+			tokens.extend(proc_macro2::TokenStream::from(item));
+
+			return tokens.into();
+		}
+	};
+
+	let parsed_item = parse_macro_input!(item as Item);
+	let ident = match variant::target_ident(&parsed_item) {
+		Ok(ident) => ident,
+		Err(err) => {
+			let mut tokens = err.into_compile_error();
+
+			parsed_item.to_tokens(&mut tokens);
+
+			return tokens.into();
+		}
+	};
+
+	if !args.inventory {
+		return parsed_item.to_token_stream().into();
+	}
+
+	let mut tokens = inventory::submit(&args, &ident);
+
+	parsed_item.to_tokens(&mut tokens);
+
+	tokens.into()
+}
+
+/// Reports every type registered with [macro@enum_builder_variant] for an enum that no
+/// [macro@enum_builder] enum builds, such as a misspelled enum name, which would otherwise silently
+/// never be discovered.
+///
+/// ## Optional Parameters
+/// #### path = [str]
+/// Override the scanned location with the provided directory path, relative to the file containing
+/// the macro. Defaults to the directory of that file. When the compiler doesn't expose that file,
+/// it's the only file of the crate invoking the macro.
+///
+/// ## Examples
+/// ```
+/// enum_builder_check!();
+/// ```
+/// ```
+/// enum_builder_check!(path = "../plugins");
+/// ```
+#[proc_macro]
+pub fn enum_builder_check(input: TokenStream) -> TokenStream {
+	let mut path = None;
+	let check_parser = parser(|meta| {
+		if meta.path.is_ident("path") {
+			path = Some(meta.value()?.parse::<LitStr>()?.value());
+
+			Ok(())
+		} else {
+			Err(meta.error("unsupported enum_builder_check parameter"))
+		}
+	});
+
+	parse_macro_input!(input with check_parser);
+
+	check::call_site_file(Span::call_site().local_file())
+		.and_then(|file| {
+			let dir = file.parent().unwrap_or(Path::new(""));
+			let root = match path {
+				Some(path) => dir.join(path),
+				None => dir.to_owned(),
+			};
+			let registrations = scan::registrations(&root, &Options::default())?;
+
+			check::check(&root, &Options::default(), registrations, None)
+		})
+		.err()
+		.map(syn::Error::into_compile_error)
+		.unwrap_or_default()
+		.into()
 }
@@ -0,0 +1,254 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+use crate::{scan::display_path, variant::Variant};
+
+/// Environment variable allowing the lockfile to be rewritten with the discovered variants.
+const UPDATE_VAR: &str = "ENUM_BUILDER_UPDATE_LOCK";
+
+/// The variants recorded for each enum in a lockfile, as pairs of variant names and files.
+type Sections = Vec<(String, Vec<(String, String)>)>;
+
+/// Entries of a section of a lockfile, as names and values along with their line numbers.
+pub(crate) type Entries = Vec<(String, String, usize)>;
+
+/// Whether `text`, following an item of a lockfile line, is blank or a comment.
+fn is_comment(text: &str) -> bool {
+	let text = text.trim();
+
+	text.is_empty() || text.starts_with('#')
+}
+
+/// Parses the value of an entry, a quoted string where `\"` and `\\` escape quotes and
+/// backslashes, optionally followed by a comment.
+fn parse_value(text: &str) -> Result<String, &'static str> {
+	let Some(quoted) = text.trim_start().strip_prefix('"') else {
+		return Err("expected a quoted value");
+	};
+	let mut chars = quoted.chars();
+	let mut value = String::new();
+
+	loop {
+		match chars.next() {
+			Some('"') => break,
+			Some('\\') => match chars.next() {
+				Some(escaped @ ('"' | '\\')) => value.push(escaped),
+				_ => return Err("unsupported escape sequence in the value"),
+			},
+			Some(c) => value.push(c),
+			None => return Err("unterminated quoted value"),
+		}
+	}
+
+	if !is_comment(chars.as_str()) {
+		return Err("unexpected text after the value");
+	}
+
+	Ok(value)
+}
+
+/// Parses the `[Section]` headers of a lockfile, or of a file in the same format, and their
+/// `Name = "value"` entries. `#` starts a comment outside of quoted values. Fails with the number
+/// of the first malformed line and what's wrong with it.
+pub(crate) fn parse_sections(text: &str) -> Result<Vec<(String, Entries)>, (usize, &'static str)> {
+	let mut sections: Vec<(String, Entries)> = vec![];
+
+	for (index, line) in text.lines().map(str::trim).enumerate() {
+		let number = index + 1;
+
+		if is_comment(line) {
+			continue;
+		}
+
+		if let Some(header) = line.strip_prefix('[') {
+			let Some((name, rest)) = header.split_once(']') else {
+				return Err((number, "expected `]` closing the section name"));
+			};
+
+			if !is_comment(rest) {
+				return Err((number, "unexpected text after the section name"));
+			}
+
+			sections.push((name.trim().to_owned(), vec![]));
+			continue;
+		}
+
+		let Some((name, value)) = line.split_once('=') else {
+			return Err((
+				number,
+				"expected a [Section] header or a Name = \"value\" entry",
+			));
+		};
+		let name = name.trim();
+
+		if name.is_empty() || name.contains(char::is_whitespace) {
+			return Err((number, "expected a name without whitespace before `=`"));
+		}
+
+		let value = parse_value(value).map_err(|message| (number, message))?;
+		let Some((_, entries)) = sections.last_mut() else {
+			return Err((number, "expected a [Section] header before the first entry"));
+		};
+
+		entries.push((name.to_owned(), value, number));
+	}
+
+	Ok(sections)
+}
+
+/// Parses the `[Enum]` sections of a lockfile and their `Variant = "file"` entries.
+fn parse(text: &str) -> Result<Sections, (usize, &'static str)> {
+	Ok(parse_sections(text)?
+		.into_iter()
+		.map(|(name, entries)| {
+			let entries = entries
+				.into_iter()
+				.map(|(variant, file, _)| (variant, file))
+				.collect();
+
+			(name, entries)
+		})
+		.collect())
+}
+
+/// Renders the sections of a lockfile, sorted by enum name.
+fn render(sections: &mut Sections) -> String {
+	let mut text = format!(
+		"# Variants discovered by enum_builder. Set {}=1 to update.\n",
+		UPDATE_VAR
+	);
+
+	sections.sort();
+
+	for (name, entries) in sections {
+		text.push_str(&format!("\n[{}]\n", name));
+
+		for (variant, file) in entries {
+			let file = file.replace('\\', "\\\\").replace('"', "\\\"");
+
+			text.push_str(&format!("{} = \"{}\"\n", variant, file));
+		}
+	}
+
+	text
+}
+
+/// Checks the variants discovered for the enum named `ident` against those recorded in the lockfile
+/// at `path`, relative to the crate root, rewriting it instead when the update variable is set.
+/// Returns a constant including the lockfile, so the enum is rebuilt when it changes.
+pub(crate) fn check(ident: &Ident, variants: &[&Variant], path: &str) -> syn::Result<TokenStream> {
+	let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path);
+	let update = env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty() && value != "0");
+	let mut sections = match parse(&fs::read_to_string(&path).unwrap_or_default()) {
+		Ok(sections) => sections,
+		// Malformed lockfiles are rewritten from scratch when updating.
+		Err(_) if update => vec![],
+		Err((line, message)) => {
+			return Err(syn::Error::new(
+				Span::call_site(),
+				format!(
+					"malformed lockfile {}:{}: {}; set {}=1 to rewrite it",
+					display_path(&path),
+					line,
+					message,
+					UPDATE_VAR
+				),
+			));
+		}
+	};
+	let name = ident.to_string();
+	let mut discovered: Vec<(String, String)> = variants
+		.iter()
+		.map(|variant| {
+			(
+				variant.name.to_string(),
+				display_path(&variant.file).replace('\\', "/"),
+			)
+		})
+		.collect();
+
+	discovered.sort();
+
+	let locked = sections.iter().position(|(section, _)| *section == name);
+
+	let file = path.to_string_lossy().into_owned();
+	let track = quote!(
+		const _: &[u8] = ::core::include_bytes!(#file);
+	);
+
+	if locked.is_some_and(|index| sections[index].1 == discovered) {
+		return Ok(track);
+	}
+
+	if update {
+		match locked {
+			Some(index) => sections[index].1 = discovered,
+			None => sections.push((name, discovered)),
+		}
+
+		fs::write(&path, render(&mut sections)).map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!("unable to write lockfile {}: {}", file, err),
+			)
+		})?;
+
+		return Ok(track);
+	}
+
+	let Some(index) = locked else {
+		return Err(syn::Error::new(
+			Span::call_site(),
+			format!(
+				"the variants of {} aren't recorded in the lockfile {}; set {}=1 to record them",
+				ident,
+				display_path(&path),
+				UPDATE_VAR
+			),
+		));
+	};
+
+	let locked = &sections[index].1;
+	let describe = |entries: Vec<&(String, String)>| {
+		entries
+			.iter()
+			.map(|(variant, file)| format!("{} ({})", variant, file))
+			.collect::<Vec<_>>()
+			.join(", ")
+	};
+	let added = describe(
+		discovered
+			.iter()
+			.filter(|entry| !locked.contains(entry))
+			.collect(),
+	);
+	let removed = describe(
+		locked
+			.iter()
+			.filter(|entry| !discovered.contains(entry))
+			.collect(),
+	);
+	let mut changes = vec![];
+
+	if !added.is_empty() {
+		changes.push(format!("added {}", added));
+	}
+
+	if !removed.is_empty() {
+		changes.push(format!("removed {}", removed));
+	}
+
+	Err(syn::Error::new(
+		Span::call_site(),
+		format!(
+			"the variants of {} differ from the lockfile {}: {}; set {}=1 to update it if this is intended",
+			ident,
+			display_path(&path),
+			changes.join("; "),
+			UPDATE_VAR
+		),
+	))
+}
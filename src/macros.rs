@@ -0,0 +1,181 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, Ident, ItemEnum, Path, Type};
+
+use crate::{case::to_snake_case, variant::Variant};
+
+/// The path of the enum's module, at `module` from the crate root when it's known, through
+/// `$crate` so that generated macros resolve it wherever they're invoked.
+fn anchor(module: Option<&[String]>) -> Option<TokenStream> {
+	let module = module?.iter().map(|segment| format_ident!("{}", segment));
+
+	Some(quote!($crate #(::#module)*))
+}
+
+/// The payload type of `variant` as named from any module, resolving paths relative to the enum's
+/// module at `module` from the crate root, and types declared in it, through `$crate`.
+fn anchored_payload(variant: &Variant, module: Option<&[String]>) -> TokenStream {
+	let payload = variant.payload();
+	let Some(mut module) = module.map(<[String]>::to_vec) else {
+		return payload;
+	};
+	let Ok(Type::Path(mut ty)) = syn::parse2::<Type>(payload.clone()) else {
+		return payload;
+	};
+
+	if ty.qself.is_some() || ty.path.leading_colon.is_some() {
+		return payload;
+	}
+
+	let relative = ty
+		.path
+		.segments
+		.first()
+		.is_some_and(|segment| segment.ident == "self" || segment.ident == "super");
+	// Other single identifiers may be imported into the enum's module or come from the prelude,
+	// so only types declared in it are anchored.
+	let declared = variant.module.is_empty()
+		&& variant.args.store.is_none()
+		&& variant.prefix.is_none()
+		&& ty.path.segments.len() == 1;
+
+	if !relative && !declared {
+		return payload;
+	}
+
+	while let Some(segment) = ty.path.segments.first() {
+		if segment.ident == "super" {
+			module.pop();
+		} else if segment.ident != "self" {
+			break;
+		}
+
+		ty.path.segments = ty.path.segments.into_iter().skip(1).collect();
+	}
+
+	let anchor = anchor(Some(&module));
+
+	quote!(#anchor::#ty)
+}
+
+/// Generates the `<enum>_for_each_variant!` macro, invoking a callback macro with the name and
+/// payload type of every discovered variant.
+pub(crate) fn for_each_variant(
+	item: &ItemEnum,
+	variants: &[Variant],
+	module: Option<&[String]>,
+) -> TokenStream {
+	let macro_ident = format_ident!(
+		"{}_for_each_variant",
+		to_snake_case(&item.ident.to_string())
+	);
+	let names = variants.iter().map(|variant| &variant.name);
+	let payloads = variants
+		.iter()
+		.map(|variant| anchored_payload(variant, module));
+
+	quote! {
+		#[allow(unused_macros)]
+		macro_rules! #macro_ident {
+			($callback:ident $(, $($args:tt)*)?) => {
+				#($callback!(#names, #payloads $(, $($args)*)?);)*
+			};
+		}
+
+		#[allow(unused_imports)]
+		pub(crate) use #macro_ident;
+	}
+}
+
+/// Invokes the user-supplied macro at `hook` for every discovered variant with the enum name, the
+/// variant name, its payload type and its metadata, so downstream crates can generate their own
+/// per-variant items.
+pub(crate) fn variant_hook(hook: &Path, item: &ItemEnum, variants: &[Variant]) -> TokenStream {
+	let ident = &item.ident;
+	let invocations = variants.iter().map(|variant| {
+		let name = &variant.name;
+		let payload = variant.payload();
+		let keys = variant
+			.args
+			.meta
+			.iter()
+			.map(|(key, _)| format_ident!("{}", key));
+		let values = variant.args.meta.iter().map(|(_, value)| value);
+
+		quote!(#hook!(#ident, #name, #payload, { #(#keys = #values),* });)
+	});
+
+	quote!(#(#invocations)*)
+}
+
+/// Generates the `match_<enum>!` macro, evaluating an expression with the payload of whichever
+/// variant a value holds.
+pub(crate) fn match_variant(item: &ItemEnum, module: Option<&[String]>) -> TokenStream {
+	let ident = &item.ident;
+	let path = match anchor(module) {
+		Some(anchor) => quote!(#anchor::#ident),
+		None => quote!(#ident),
+	};
+	let macro_ident = format_ident!("match_{}", to_snake_case(&ident.to_string()));
+	let names: Vec<&Ident> = item
+		.variants
+		.iter()
+		.filter(
+			|variant| matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1),
+		)
+		.map(|variant| &variant.ident)
+		.collect();
+
+	quote! {
+		#[allow(unused_macros)]
+		macro_rules! #macro_ident {
+			($value:expr, $inner:ident => $body:expr) => {
+				match $value {
+					#(#path::#names($inner) => $body,)*
+				}
+			};
+			($value:expr, $inner:ident => $body:expr, _ => $fallback:expr) => {
+				match $value {
+					#(#path::#names($inner) => $body,)*
+					#[allow(unreachable_patterns)]
+					_ => $fallback,
+				}
+			};
+		}
+
+		#[allow(unused_imports)]
+		pub(crate) use #macro_ident;
+	}
+}
+
+/// Generates the `assert_<enum>_variants!` macro, failing to compile unless it lists exactly the
+/// discovered variants, in any order.
+pub(crate) fn assert_variants(item: &ItemEnum, names: &[&Ident]) -> TokenStream {
+	let macro_ident = format_ident!("assert_{}_variants", to_snake_case(&item.ident.to_string()));
+
+	quote! {
+		#[allow(unused_macros)]
+		macro_rules! #macro_ident {
+			($($name:ident),* $(,)?) => {
+				// Matching every discovered variant against an enum of the listed ones fails both
+				// when one isn't listed and, as the match isn't exhaustive, when one isn't discovered.
+				const _: () = {
+					#[allow(dead_code)]
+					enum Listed {
+						$($name),*
+					}
+
+					#[allow(dead_code)]
+					fn assert_listed(listed: Listed) {
+						match listed {
+							#(Listed::#names => {})*
+						}
+					}
+				};
+			};
+		}
+
+		#[allow(unused_imports)]
+		pub(crate) use #macro_ident;
+	}
+}
@@ -0,0 +1,105 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::Span;
+use syn::ItemEnum;
+
+use crate::{options::Options, scan::display_path, variant::Variant};
+
+/// Quotes `value` as a JSON string.
+fn json_string(value: &str) -> String {
+	let mut quoted = String::from('"');
+
+	for c in value.chars() {
+		match c {
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+			c => quoted.push(c),
+		}
+	}
+
+	quoted.push('"');
+	quoted
+}
+
+/// Renders the manifest of the variants discovered for the enum as JSON.
+fn render(options: &Options, item: &ItemEnum, variants: &[Variant]) -> String {
+	let entries: Vec<String> = variants
+		.iter()
+		.map(|variant| {
+			let payload: String = variant
+				.payload()
+				.to_string()
+				.split_whitespace()
+				.collect::<String>()
+				.replace(',', ", ");
+			let module = if variant.module.is_empty() {
+				"self".to_owned()
+			} else {
+				variant
+					.module_qualifier()
+					.iter()
+					.map(ToString::to_string)
+					.collect::<Vec<_>>()
+					.join("::")
+			};
+
+			format!(
+				"\t\t{{\"name\": {}, \"type\": {}, \"file\": {}, \"line\": {}, \"module\": {}}}",
+				json_string(&options.external_name(&variant.name)),
+				json_string(&payload),
+				json_string(&display_path(&variant.file)),
+				variant.line,
+				json_string(&module),
+			)
+		})
+		.collect();
+
+	format!(
+		"{{\n\t\"enum\": {},\n\t\"variants\": [\n{}\n\t]\n}}\n",
+		json_string(&item.ident.to_string()),
+		entries.join(",\n")
+	)
+}
+
+/// Writes the JSON manifest of the variants discovered for the enum to `path`, relative to the
+/// crate root, or to `OUT_DIR/enum_builder/<Enum>.json` if no path is provided.
+pub(crate) fn write(
+	options: &Options,
+	item: &ItemEnum,
+	variants: &[Variant],
+	path: Option<&str>,
+) -> syn::Result<()> {
+	let path = match path {
+		Some(path) => {
+			PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path)
+		}
+		None => {
+			let Some(out_dir) = env::var_os("OUT_DIR") else {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					"the manifest parameter needs a path when the crate has no build script to provide OUT_DIR",
+				));
+			};
+
+			PathBuf::from(out_dir)
+				.join("enum_builder")
+				.join(format!("{}.json", item.ident))
+		}
+	};
+
+	path.parent()
+		.map_or(Ok(()), fs::create_dir_all)
+		.and_then(|_| fs::write(&path, render(options, item, variants)))
+		.map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!(
+					"unable to write manifest to {}: {}",
+					path.to_string_lossy(),
+					err
+				),
+			)
+		})
+}
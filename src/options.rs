@@ -0,0 +1,656 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+	Attribute, Ident, LitBool, LitInt, LitStr, Meta, Path, Token, Type, TypeParamBound, Variant,
+	Visibility, bracketed, meta::ParseNestedMeta, parenthesized, parse_quote,
+	punctuated::Punctuated, token,
+};
+
+use crate::{case, edition};
+
+/// Parameters accepted by [macro@crate::enum_builder].
+#[derive(Default)]
+pub(crate) struct Options {
+	/// Overrides the variant scan location, relative to the file containing the macro.
+	pub path: Option<String>,
+	/// Environment variable naming the directory `path` is relative to, like `OUT_DIR`.
+	pub path_env: Option<String>,
+	/// Walks into build output, hidden and vendored directories too.
+	pub scan_all: bool,
+	/// Files scanned instead of walking the scan location, relative to it.
+	pub files: Option<Vec<String>>,
+	/// File listing variants along with the discovered ones, relative to the crate root.
+	pub registry_file: Option<String>,
+	/// Edition the scanned sources are parsed with.
+	pub edition: Option<String>,
+	/// Caches scanned sources across compilations, in the provided directory if any.
+	pub cache: Option<Option<String>>,
+	/// Trait whose implementors are discovered as variants without being registered.
+	pub impls: Option<Ident>,
+	/// Ignores discovered types that aren't declared `pub`.
+	pub only_pub: bool,
+	/// Patterns one of which discovered type names must match, if any.
+	pub include: Vec<String>,
+	/// Patterns discovered type names must not match.
+	pub exclude: Vec<String>,
+	/// Derives attached to the generated enum after every other attribute.
+	pub derive: Vec<Path>,
+	/// Serde container attributes, deriving `Serialize` and `Deserialize` when provided.
+	pub serde: Option<TokenStream>,
+	/// Derives `schemars::JsonSchema` for the generated enum.
+	pub schemars: bool,
+	/// strum derives attached to the generated enum and its kind companion.
+	pub strum: Vec<Path>,
+	/// Overrides the visibility of the generated enum and its helper items.
+	pub vis: Option<Visibility>,
+	/// Marks the generated enum `#[non_exhaustive]`.
+	pub non_exhaustive: bool,
+	/// Prefix removed from discovered type names when forming variant names.
+	pub strip_prefix: Option<String>,
+	/// Suffix removed from discovered type names when forming variant names.
+	pub strip_suffix: Option<String>,
+	/// Refers to payload types through the path of their declaring module.
+	pub qualified_paths: bool,
+	/// Re-exports every payload type declared outside the enum's module next to the enum.
+	pub reexport: bool,
+	/// Another enum_builder enum whose variants are included in this one.
+	pub extends: Option<Ident>,
+	/// Another enum_builder enum whose variants, filtered by `tag`, make up this one.
+	pub source: Option<Ident>,
+	/// Tag the variants taken from `source` must carry.
+	pub tag: Option<String>,
+	/// Moves variants declared in child modules into one inner enum per module.
+	pub group_by_module: bool,
+	/// Prefixes colliding variant names with the name of their declaring module.
+	pub disambiguate: bool,
+	/// Generates field-less variants instead of variants holding the discovered types.
+	pub unit: bool,
+	/// Primitive representation of the generated enum.
+	pub repr: Option<Ident>,
+	/// Generates a `stable_id()` method returning a hash of each variant name.
+	pub stable_id: bool,
+	/// Catch-all variant appended after the discovered ones.
+	pub other: Option<Variant>,
+	/// Generates `impl Default` constructing the variant registered with `default`.
+	pub default: bool,
+	/// Generates the field-less companion kind enum and `kind()` method.
+	pub kind: bool,
+	/// Overrides the name of the companion kind enum.
+	pub kind_name: Option<Ident>,
+	/// Derives `clap::ValueEnum` for the kind enum.
+	pub clap: bool,
+	/// Generates `same_kind()` and the wrapper comparing values only by variant.
+	pub same_kind: bool,
+	/// Generates `from_name()` and `FromStr` for the kind enum.
+	pub from_str: bool,
+	/// Matches names case-insensitively when parsing kinds.
+	pub case_insensitive: bool,
+	/// Generates per-variant `is_*()` predicates and payload accessors.
+	pub accessors: bool,
+	/// Generates `From` for the enum from every payload type.
+	pub from: bool,
+	/// Generates `TryFrom<Enum>` for every payload type.
+	pub try_from: bool,
+	/// Generates the `<enum>_for_each_variant!` macro.
+	pub for_each_variant: bool,
+	/// Macro invoked for every discovered variant with its name, payload type and metadata.
+	pub variant_macro: Option<syn::Path>,
+	/// Generates the `match_<enum>!` macro.
+	pub match_variant: bool,
+	/// Generates the `assert_<enum>_variants!` macro.
+	pub assert_variants: bool,
+	/// Generates the visitor trait and `accept()` method.
+	pub visitor: bool,
+	/// Generates the borrowed companion enum and `as_ref()`.
+	pub as_ref: bool,
+	/// Generates the mutably borrowed companion enum and `as_mut()`.
+	pub as_mut: bool,
+	/// Generates `all_default()` constructing every variant.
+	pub all_default: bool,
+	/// Implements `arbitrary::Arbitrary` for the enum.
+	pub arbitrary: bool,
+	/// Generates `arbitrary_<enum>()` returning a proptest strategy.
+	pub proptest: bool,
+	/// Traits implemented for the enum by forwarding to the payload of each variant.
+	pub dispatch: Vec<Ident>,
+	/// Trait boxed by the `Dynamic` variant appended after the discovered ones.
+	pub dyn_fallback: Option<Ident>,
+	/// Generates the static registry of variant descriptors.
+	pub registry: bool,
+	/// Generates `from_name()` constructing variants through the registry.
+	pub from_name: bool,
+	/// Type of the configuration registry constructors take a reference to.
+	pub config: Option<Type>,
+	/// Generates `from_tagged_value()` deserializing payloads with serde_json.
+	pub serde_factory: bool,
+	/// Generates `Display` writing variant names.
+	pub display: bool,
+	/// Generates `Debug` printing the path of each payload type along with the payload.
+	pub verbose_debug: bool,
+	/// Case convention of the variant names written and parsed by generated code.
+	pub rename_all: Option<String>,
+	/// Generates `Display`, `Error` and `From` forwarding to the payloads, for error enums.
+	pub error: bool,
+	/// Generates `VARIANT_NAMES` and `name()`.
+	pub names: bool,
+	/// Generates `COUNT` and `variant_index()`.
+	pub index: bool,
+	/// Generates `module_path()`.
+	pub module_path: bool,
+	/// Generates `source_location()`.
+	pub source_location: bool,
+	/// Generates `type_name()`.
+	pub type_name: bool,
+	/// Generates `type_ids()`, `kind_of()` and `holds()`.
+	pub type_ids: bool,
+	/// Generates `tags()`.
+	pub tags: bool,
+	/// Generates `VARIANT_SIZES`, warning about variants larger than the others by the provided
+	/// ratio if any.
+	pub variant_sizes: Option<Option<usize>>,
+	/// Generates the `metadata()` accessor and static metadata table.
+	pub metadata: bool,
+	/// Lockfile the discovered variants are checked against.
+	pub lock: Option<String>,
+	/// Writes a JSON manifest of the discovered variants, to the provided path if any.
+	pub manifest: Option<Option<String>>,
+	/// Checked-in file the expansion is included from, once checked to be up to date.
+	pub pregenerated: Option<String>,
+	/// Module the enum and every generated item are declared in, re-exporting the enum.
+	pub mod_name: Option<Ident>,
+	/// Dumps the formatted expansion for debugging.
+	pub debug: bool,
+	/// Reports statistics about the scan of the variants, to the provided path if any.
+	pub stats: Option<Option<String>>,
+	/// Hides every generated item other than the enum from rustdoc.
+	pub hide_helpers: bool,
+	/// Refers to `alloc` types through the `alloc` crate rather than the std prelude.
+	pub no_std: bool,
+	/// Builds the enum without variants when none are found, instead of failing.
+	pub allow_empty: bool,
+	/// Number of variants that must be discovered.
+	pub expect_count: Option<usize>,
+	/// Fails on registrations found in the scanned sources for enums that nothing in the crate
+	/// builds.
+	pub strict_targets: bool,
+	/// Warns about registrations found in the scanned sources for enums that nothing in the crate
+	/// builds.
+	pub warn_targets: bool,
+	/// Bounds every discovered payload is statically asserted to satisfy.
+	pub must_impl: Option<Punctuated<TypeParamBound, Token![+]>>,
+	/// Statically asserts every discovered payload is `Send` and `Sync`.
+	pub assert_send_sync: bool,
+	/// Generates the registration type collected with `inventory`.
+	pub inventory: bool,
+}
+
+/// Parses the value of a parameter accepting either a string or a list of strings.
+fn parse_strings(meta: &ParseNestedMeta) -> syn::Result<Vec<LitStr>> {
+	let value = meta.value()?;
+
+	if !value.peek(token::Bracket) {
+		return Ok(vec![value.parse()?]);
+	}
+
+	let content;
+
+	bracketed!(content in value);
+
+	Ok(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+		.into_iter()
+		.collect())
+}
+
+impl Options {
+	/// Parses the parameters of an `#[enum_builder(...)]` attribute found while scanning.
+	pub fn from_attr(attr: &Attribute) -> syn::Result<Self> {
+		let mut options = Self::default();
+
+		if let Meta::List(list) = &attr.meta {
+			list.parse_nested_meta(|meta| options.parse(meta))?;
+		}
+
+		Ok(options)
+	}
+
+	pub fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+		if meta.path.is_ident("path") {
+			self.path = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("path_env") {
+			self.path_env = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("scan_all") {
+			self.scan_all = true;
+		} else if meta.path.is_ident("files") {
+			self.files
+				.get_or_insert_default()
+				.extend(parse_strings(&meta)?.iter().map(LitStr::value));
+		} else if meta.path.is_ident("edition") {
+			self.edition = Some(edition::parse(&meta.value()?.parse()?)?);
+		} else if meta.path.is_ident("cache") {
+			self.cache = Some(if meta.input.peek(Token![=]) {
+				Some(meta.value()?.parse::<LitStr>()?.value())
+			} else {
+				None
+			});
+		} else if meta.path.is_ident("impls") {
+			self.impls = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("only_pub") {
+			self.only_pub = true;
+		} else if meta.path.is_ident("include") {
+			self.include
+				.extend(parse_strings(&meta)?.iter().map(LitStr::value));
+		} else if meta.path.is_ident("exclude") {
+			self.exclude
+				.extend(parse_strings(&meta)?.iter().map(LitStr::value));
+		} else if meta.path.is_ident("derive") {
+			meta.parse_nested_meta(|derive| {
+				self.derive.push(derive.path);
+
+				Ok(())
+			})?;
+		} else if meta.path.is_ident("serde") {
+			let content;
+
+			parenthesized!(content in meta.input);
+			self.serde = Some(content.parse()?);
+		} else if meta.path.is_ident("schemars") {
+			if !cfg!(feature = "schemars") {
+				return Err(meta.error(
+					"the schemars parameter requires the schemars feature of enum_builder",
+				));
+			}
+
+			self.schemars = true;
+		} else if meta.path.is_ident("strum") {
+			meta.parse_nested_meta(|derive| {
+				self.strum.push(derive.path);
+
+				Ok(())
+			})?;
+		} else if meta.path.is_ident("vis") {
+			self.vis = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("non_exhaustive") {
+			self.non_exhaustive = true;
+		} else if meta.path.is_ident("strip_prefix") {
+			self.strip_prefix = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("strip_suffix") {
+			self.strip_suffix = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("qualified_paths") {
+			self.qualified_paths = true;
+		} else if meta.path.is_ident("reexport") {
+			self.reexport = true;
+		} else if meta.path.is_ident("extends") {
+			self.extends = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("group_by_module") {
+			self.group_by_module = true;
+		} else if meta.path.is_ident("disambiguate") {
+			self.disambiguate = true;
+		} else if meta.path.is_ident("unit") {
+			self.unit = true;
+		} else if meta.path.is_ident("repr") {
+			self.repr = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("stable_id") {
+			self.stable_id = true;
+		} else if meta.path.is_ident("other") {
+			self.other = Some(if meta.input.peek(Token![=]) {
+				meta.value()?.parse::<LitStr>()?.parse()?
+			} else {
+				parse_quote!(Other)
+			});
+		} else if meta.path.is_ident("default") {
+			self.default = true;
+		} else if meta.path.is_ident("kind") {
+			self.kind = true;
+
+			if meta.input.peek(Token![=]) {
+				self.kind_name = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+			}
+		} else if meta.path.is_ident("clap") {
+			if !cfg!(feature = "clap") {
+				return Err(
+					meta.error("the clap parameter requires the clap feature of enum_builder")
+				);
+			}
+
+			self.clap = true;
+		} else if meta.path.is_ident("same_kind") {
+			self.same_kind = true;
+		} else if meta.path.is_ident("from_str") {
+			self.from_str = true;
+		} else if meta.path.is_ident("case_insensitive") {
+			self.case_insensitive = true;
+		} else if meta.path.is_ident("accessors") {
+			self.accessors = true;
+		} else if meta.path.is_ident("from") {
+			if meta.input.peek(Token![=]) {
+				self.source = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+			} else {
+				self.from = true;
+			}
+		} else if meta.path.is_ident("tag") {
+			self.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("try_from") {
+			self.try_from = true;
+		} else if meta.path.is_ident("for_each_variant") {
+			self.for_each_variant = true;
+		} else if meta.path.is_ident("variant_macro") {
+			self.variant_macro = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("match_variant") {
+			self.match_variant = true;
+		} else if meta.path.is_ident("assert_variants") {
+			self.assert_variants = true;
+		} else if meta.path.is_ident("visitor") {
+			self.visitor = true;
+		} else if meta.path.is_ident("as_ref") {
+			self.as_ref = true;
+		} else if meta.path.is_ident("as_mut") {
+			self.as_mut = true;
+		} else if meta.path.is_ident("all_default") {
+			self.all_default = true;
+		} else if meta.path.is_ident("arbitrary") {
+			if !cfg!(feature = "arbitrary") {
+				return Err(meta.error(
+					"the arbitrary parameter requires the arbitrary feature of enum_builder",
+				));
+			}
+
+			self.arbitrary = true;
+		} else if meta.path.is_ident("proptest") {
+			if !cfg!(feature = "proptest") {
+				return Err(meta.error(
+					"the proptest parameter requires the proptest feature of enum_builder",
+				));
+			}
+
+			self.proptest = true;
+		} else if meta.path.is_ident("dispatch") {
+			for name in parse_strings(&meta)? {
+				self.dispatch.push(name.parse()?);
+			}
+		} else if meta.path.is_ident("dyn_fallback") {
+			self.dyn_fallback = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("registry") {
+			self.registry = true;
+		} else if meta.path.is_ident("registry_file") {
+			self.registry_file = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("from_name") {
+			self.from_name = true;
+		} else if meta.path.is_ident("config") {
+			self.config = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("serde_factory") {
+			if !cfg!(feature = "serde_factory") {
+				return Err(meta.error(
+					"the serde_factory parameter requires the serde_factory feature of enum_builder",
+				));
+			}
+
+			self.serde_factory = true;
+		} else if meta.path.is_ident("display") {
+			self.display = true;
+		} else if meta.path.is_ident("debug_impl") {
+			let lit = meta.value()?.parse::<LitStr>()?;
+
+			if lit.value() != "verbose" {
+				return Err(syn::Error::new_spanned(
+					&lit,
+					format!("unknown debug_impl style {}, expected verbose", lit.value()),
+				));
+			}
+
+			self.verbose_debug = true;
+		} else if meta.path.is_ident("rename_all") {
+			let lit = meta.value()?.parse::<LitStr>()?;
+
+			if !case::CONVENTIONS.contains(&lit.value().as_str()) {
+				return Err(syn::Error::new_spanned(
+					&lit,
+					format!(
+						"unknown case convention {}, expected one of {}",
+						lit.value(),
+						case::CONVENTIONS.join(", ")
+					),
+				));
+			}
+
+			self.rename_all = Some(lit.value());
+		} else if meta.path.is_ident("error") {
+			self.error = true;
+		} else if meta.path.is_ident("names") {
+			self.names = true;
+		} else if meta.path.is_ident("index") {
+			self.index = true;
+		} else if meta.path.is_ident("module_path") {
+			self.module_path = true;
+		} else if meta.path.is_ident("source_location") {
+			self.source_location = true;
+		} else if meta.path.is_ident("type_name") {
+			self.type_name = true;
+		} else if meta.path.is_ident("type_ids") {
+			self.type_ids = true;
+		} else if meta.path.is_ident("tags") {
+			self.tags = true;
+		} else if meta.path.is_ident("variant_sizes") {
+			self.variant_sizes = Some(if meta.input.peek(Token![=]) {
+				Some(meta.value()?.parse::<LitInt>()?.base10_parse()?)
+			} else {
+				None
+			});
+		} else if meta.path.is_ident("metadata") {
+			self.metadata = true;
+		} else if meta.path.is_ident("manifest") {
+			self.manifest = Some(if meta.input.peek(Token![=]) {
+				Some(meta.value()?.parse::<LitStr>()?.value())
+			} else {
+				None
+			});
+		} else if meta.path.is_ident("debug") {
+			self.debug = true;
+		} else if meta.path.is_ident("stats") {
+			self.stats = Some(if meta.input.peek(Token![=]) {
+				Some(meta.value()?.parse::<LitStr>()?.value())
+			} else {
+				None
+			});
+		} else if meta.path.is_ident("helpers_doc") {
+			let lit = meta.value()?.parse::<LitStr>()?;
+
+			self.hide_helpers = match lit.value().as_str() {
+				"hidden" => true,
+				"visible" => false,
+				value => {
+					return Err(syn::Error::new_spanned(
+						&lit,
+						format!(
+							"unknown helpers_doc value {}, expected hidden or visible",
+							value
+						),
+					));
+				}
+			};
+		} else if meta.path.is_ident("allow_empty") {
+			self.allow_empty =
+				!meta.input.peek(Token![=]) || meta.value()?.parse::<LitBool>()?.value;
+		} else if meta.path.is_ident("expect_count") {
+			self.expect_count = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+		} else if meta.path.is_ident("strict_targets") {
+			self.strict_targets = true;
+		} else if meta.path.is_ident("warn_targets") {
+			self.warn_targets = true;
+		} else if meta.path.is_ident("must_impl") {
+			self.must_impl = Some(
+				meta.value()?
+					.parse::<LitStr>()?
+					.parse_with(Punctuated::parse_separated_nonempty)?,
+			);
+		} else if meta.path.is_ident("assert_send_sync") {
+			self.assert_send_sync = true;
+		} else if meta.path.is_ident("inventory") {
+			if !cfg!(feature = "inventory") {
+				return Err(meta.error(
+					"the inventory parameter requires the inventory feature of enum_builder",
+				));
+			}
+
+			self.inventory = true;
+		} else if meta.path.is_ident("pregenerated") {
+			self.pregenerated = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("lock") {
+			self.lock = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("mod_name") {
+			self.mod_name = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+		} else if meta.path.is_ident("no_std") {
+			self.no_std = true;
+		} else {
+			return Err(meta.error("unsupported enum_builder parameter"));
+		}
+
+		Ok(())
+	}
+
+	/// Rejects parameters that depend on other parameters that weren't provided, or that conflict
+	/// with other parameters.
+	pub fn validate(&self) -> syn::Result<()> {
+		let requirements = [
+			("from_str", self.from_str, "kind", self.kind),
+			(
+				"case_insensitive",
+				self.case_insensitive,
+				"from_str",
+				self.from_str,
+			),
+			("from_name", self.from_name, "registry", self.registry),
+			("config", self.config.is_some(), "registry", self.registry),
+			("clap", self.clap, "kind", self.kind),
+			("type_ids", self.type_ids, "kind", self.kind),
+			(
+				"tag",
+				self.tag.is_some(),
+				"from = [str]",
+				self.source.is_some(),
+			),
+		];
+
+		for (name, enabled, required, provided) in requirements {
+			if enabled && !provided {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!("the {} parameter requires the {} parameter", name, required),
+				));
+			}
+		}
+
+		let conflicts = [
+			(
+				"warn_targets",
+				self.warn_targets,
+				"strict_targets",
+				self.strict_targets,
+			),
+			// Both implement FromStr for the kind enum.
+			(
+				"from_str",
+				self.from_str,
+				"strum(EnumString)",
+				self.strum.iter().any(|path| path.is_ident("EnumString")),
+			),
+			("from", self.from, "unit", self.unit),
+			("try_from", self.try_from, "unit", self.unit),
+			("serde_factory", self.serde_factory, "unit", self.unit),
+			(
+				"dyn_fallback",
+				self.dyn_fallback.is_some(),
+				"unit",
+				self.unit,
+			),
+			("error", self.error, "display", self.display),
+			("error", self.error, "unit", self.unit),
+			("error", self.error, "group_by_module", self.group_by_module),
+			(
+				"debug_impl",
+				self.verbose_debug,
+				"dyn_fallback",
+				self.dyn_fallback.is_some(),
+			),
+			(
+				"arbitrary",
+				self.arbitrary,
+				"dyn_fallback",
+				self.dyn_fallback.is_some(),
+			),
+			(
+				"proptest",
+				self.proptest,
+				"dyn_fallback",
+				self.dyn_fallback.is_some(),
+			),
+			(
+				"from = [str]",
+				self.source.is_some(),
+				"extends",
+				self.extends.is_some(),
+			),
+			(
+				"from = [str]",
+				self.source.is_some(),
+				"group_by_module",
+				self.group_by_module,
+			),
+			(
+				"from = [str]",
+				self.source.is_some(),
+				"dyn_fallback",
+				self.dyn_fallback.is_some(),
+			),
+			(
+				"from = [str]",
+				self.source.is_some(),
+				"other",
+				self.other.is_some(),
+			),
+		];
+
+		for (name, enabled, conflicting, provided) in conflicts {
+			if enabled && provided {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"the {} parameter can't be combined with the {} parameter",
+						name, conflicting
+					),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Name of the companion kind enum generated for `enum_ident`.
+	pub fn kind_ident(&self, enum_ident: &Ident) -> Ident {
+		self.kind_name
+			.clone()
+			.unwrap_or_else(|| format_ident!("{}Kind", enum_ident))
+	}
+
+	/// Name of a variant in the strings written and parsed by generated code.
+	pub fn external_name(&self, ident: &Ident) -> String {
+		case::rename(&ident.to_string(), self.rename_all.as_deref())
+	}
+
+	/// Path of `Box` in generated code.
+	pub fn box_path(&self) -> TokenStream {
+		if self.no_std {
+			quote!(::alloc::boxed::Box)
+		} else {
+			quote!(::std::boxed::Box)
+		}
+	}
+
+	/// Path of `String` in generated code.
+	pub fn string_path(&self) -> TokenStream {
+		if self.no_std {
+			quote!(::alloc::string::String)
+		} else {
+			quote!(::std::string::String)
+		}
+	}
+}
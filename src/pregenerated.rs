@@ -0,0 +1,108 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::Ident;
+
+use crate::{debug, scan::display_path};
+
+/// Environment variable allowing pregenerated expansions to be rewritten.
+const UPDATE_VAR: &str = "ENUM_BUILDER_UPDATE_PREGENERATED";
+
+/// The tokens of `tokens` as strings, ignoring trailing commas and the spacing of punctuation, which
+/// rustfmt may alter.
+fn normalize(tokens: TokenStream) -> Vec<String> {
+	let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+	let mut normalized = vec![];
+
+	for (index, token) in tokens.iter().enumerate() {
+		match token {
+			TokenTree::Group(group) => {
+				let (open, close) = match group.delimiter() {
+					Delimiter::Parenthesis => ("(", ")"),
+					Delimiter::Brace => ("{", "}"),
+					Delimiter::Bracket => ("[", "]"),
+					Delimiter::None => ("", ""),
+				};
+
+				normalized.push(open.to_owned());
+				normalized.extend(normalize(group.stream()));
+				normalized.push(close.to_owned());
+			}
+			TokenTree::Punct(punct) if punct.as_char() == ',' && index + 1 == tokens.len() => {}
+			token => normalized.push(token.to_string()),
+		}
+	}
+
+	normalized.retain(|token| !token.is_empty());
+
+	normalized
+}
+
+/// Whether `existing`, the content of a pregenerated file, holds `tokens`. The tokens are compared
+/// rather than the text, so the comparison doesn't depend on how rustfmt formatted the file, or on
+/// whether it's available.
+fn is_current(existing: &str, tokens: &TokenStream, source: &str) -> bool {
+	// The header is a regular comment, so it isn't part of the parsed tokens.
+	let Ok(existing) = existing.parse::<TokenStream>() else {
+		return false;
+	};
+	let existing = normalize(existing);
+
+	existing == normalize(tokens.clone())
+		|| source
+			.parse::<TokenStream>()
+			.is_ok_and(|source| existing == normalize(source))
+}
+
+/// Checks that the file at `path`, relative to the crate root, holds `tokens`, the expansion of the
+/// enum named `ident`, rewriting it instead when the update variable is set. Returns an `include!`
+/// of the file, so the enum is declared by it.
+pub(crate) fn check(ident: &Ident, tokens: &TokenStream, path: &str) -> syn::Result<TokenStream> {
+	let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path);
+	let file = path.to_string_lossy().into_owned();
+	let include = quote!(::core::include!(#file););
+	let existing = fs::read_to_string(&path).ok();
+	let source = format!(
+		"// @generated by enum_builder from {}. Set {}=1 to regenerate.\n\n{}",
+		ident,
+		UPDATE_VAR,
+		debug::format(tokens)
+	);
+
+	if existing
+		.as_ref()
+		.is_some_and(|existing| is_current(existing, tokens, &source))
+	{
+		return Ok(include);
+	}
+
+	if env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty() && value != "0") {
+		path.parent()
+			.map_or(Ok(()), fs::create_dir_all)
+			.and_then(|_| fs::write(&path, source))
+			.map_err(|err| {
+				syn::Error::new(
+					Span::call_site(),
+					format!("unable to write {}: {}", file, err),
+				)
+			})?;
+
+		return Ok(include);
+	}
+
+	Err(syn::Error::new(
+		Span::call_site(),
+		format!(
+			"the pregenerated expansion of {} at {} is {}; set {}=1 to regenerate it",
+			ident,
+			display_path(&path),
+			if existing.is_some() {
+				"out of date"
+			} else {
+				"missing"
+			},
+			UPDATE_VAR
+		),
+	))
+}
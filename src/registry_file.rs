@@ -0,0 +1,116 @@
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Generics, Ident, PathArguments, Type, Visibility};
+
+use crate::{
+	lock,
+	scan::display_path,
+	variant::{Variant, VariantArgs},
+};
+
+/// Path of the registry file at `path`, relative to the crate root.
+fn resolve(path: &str) -> PathBuf {
+	PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path)
+}
+
+/// Parses the variant named `name` holding the type at `ty`, declared on `line` of the registry
+/// file at `file` for the enum named `enum_name`.
+fn variant(
+	enum_name: &Ident,
+	name: &str,
+	ty: &str,
+	file: &Path,
+	line: usize,
+) -> syn::Result<Variant> {
+	let location = format!("{}:{}", display_path(file), line);
+	let error = |message: String| syn::Error::new(Span::call_site(), message);
+	let name: Ident = syn::parse_str(name)
+		.map_err(|_| error(format!("invalid variant name {} at {}", name, location)))?;
+	let path = match syn::parse_str(ty) {
+		Ok(Type::Path(ty)) if ty.qself.is_none() => ty.path,
+		_ => {
+			return Err(error(format!(
+				"invalid payload type {} at {}, expected a type path",
+				ty, location
+			)));
+		}
+	};
+	let mut segments: Vec<_> = path.segments.into_iter().collect();
+	let last = segments.pop().unwrap();
+	let leading_colon = path.leading_colon;
+	let arguments = match last.arguments {
+		PathArguments::AngleBracketed(arguments) => {
+			let arguments = arguments.args;
+
+			Some(quote!(#arguments))
+		}
+		_ => None,
+	};
+	let mut args = VariantArgs::new(enum_name.clone());
+
+	args.name = Some(name.clone());
+
+	Ok(Variant {
+		name,
+		vis: Visibility::Inherited,
+		ident: last.ident,
+		generics: Generics::default(),
+		arguments,
+		prefix: Some(quote!(#leading_colon #(#segments::)*)),
+		args,
+		module: vec![],
+		qualifier: vec![],
+		file: file.to_owned(),
+		line,
+	})
+}
+
+/// Reads the variants listed for the enum named `enum_name` in the registry file at `path`,
+/// relative to the crate root, whose `[Enum]` sections list `Variant = "path::to::Type"` entries.
+/// Type paths are resolved from the enum's module.
+pub(crate) fn load(enum_name: &Ident, path: &str) -> syn::Result<Vec<Variant>> {
+	let file = resolve(path);
+	let text = fs::read_to_string(&file).map_err(|err| {
+		syn::Error::new(
+			Span::call_site(),
+			format!(
+				"unable to read registry file {}: {}",
+				display_path(&file),
+				err
+			),
+		)
+	})?;
+	let sections = lock::parse_sections(&text).map_err(|(line, message)| {
+		syn::Error::new(
+			Span::call_site(),
+			format!(
+				"malformed registry file {}:{}: {}",
+				display_path(&file),
+				line,
+				message
+			),
+		)
+	})?;
+
+	sections
+		.iter()
+		.filter(|(section, _)| *enum_name == section)
+		.flat_map(|(_, entries)| entries)
+		.map(|(name, ty, line)| variant(enum_name, name, ty, &file, *line))
+		.collect()
+}
+
+/// Generates a constant including the registry file at `path`, so the enum is rebuilt when it
+/// changes.
+pub(crate) fn track(path: &str) -> TokenStream {
+	let file = resolve(path).to_string_lossy().into_owned();
+
+	quote!(
+		const _: &[u8] = ::core::include_bytes!(#file);
+	)
+}
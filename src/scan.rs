@@ -0,0 +1,724 @@
+use std::{
+	ffi::OsStr,
+	fs,
+	path::{Component, Path, PathBuf},
+	time::Instant,
+};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+	Attribute, File, GenericParam, Generics, Ident, Item, ItemEnum, ItemTrait, ItemType, Token,
+	Type, Visibility,
+	parse::{ParseStream, Parser},
+};
+use walkdir::WalkDir;
+
+use crate::{
+	cache,
+	decl::Decl,
+	edition,
+	expand::stable_hash,
+	options::Options,
+	stats,
+	variant::{ENUM_BUILDER_VARIANT, Variant, VariantArgs, impl_target},
+};
+
+const ENUM_BUILDER: &str = "enum_builder";
+pub(crate) const ENUM_BUILDER_DECL: &str = "enum_builder_decl";
+
+fn is_ident_char(c: char) -> bool {
+	c.is_alphanumeric() || c == '_'
+}
+
+/// Blanks out the comments and string literals of `src`, keeping its line breaks and byte offsets,
+/// so that text in them isn't mistaken for code.
+fn blank_comments_and_strings(src: &str) -> String {
+	let bytes = src.as_bytes();
+	let mut blanked = bytes.to_vec();
+	let mut index = 0;
+
+	while index < bytes.len() {
+		let rest = &bytes[index..];
+		let start = index;
+
+		if rest.starts_with(b"//") {
+			index += rest
+				.iter()
+				.position(|byte| *byte == b'\n')
+				.unwrap_or(rest.len());
+		} else if rest.starts_with(b"/*") {
+			let mut depth = 0;
+
+			while index < bytes.len() {
+				if bytes[index..].starts_with(b"/*") {
+					depth += 1;
+					index += 2;
+				} else if bytes[index..].starts_with(b"*/") {
+					depth -= 1;
+					index += 2;
+
+					if depth == 0 {
+						break;
+					}
+				} else {
+					index += 1;
+				}
+			}
+		} else if rest[0] == b'"' {
+			index += 1;
+
+			while index < bytes.len() && bytes[index] != b'"' {
+				index += if bytes[index] == b'\\' { 2 } else { 1 };
+			}
+
+			index += 1;
+		} else if rest[0] == b'r'
+			&& (index == 0 || !is_ident_char(bytes[index - 1] as char))
+			&& let Some(hashes) = rest[1..].iter().position(|byte| *byte != b'#')
+			&& rest[1 + hashes] == b'"'
+		{
+			let terminator = [b"\"".as_slice(), &vec![b'#'; hashes]].concat();
+
+			index += hashes + 2;
+			index += bytes[index..]
+				.windows(terminator.len())
+				.position(|window| window == terminator)
+				.map_or(bytes.len() - index, |end| end + terminator.len());
+		} else if rest.starts_with(b"'\"'") || rest.starts_with(b"'\\\"'") {
+			// Quotes in character literals don't start string literals.
+			index += if rest[1] == b'"' { 3 } else { 4 };
+			continue;
+		} else {
+			index += 1;
+			continue;
+		}
+
+		for byte in &mut blanked[start..index.min(bytes.len())] {
+			if *byte != b'\n' {
+				*byte = b' ';
+			}
+		}
+	}
+
+	String::from_utf8_lossy(&blanked).into_owned()
+}
+
+/// Finds the line declaring `keyword ident` in `src`, outside comments and string literals.
+///
+/// Spans of tokens parsed from a string carry no location information, so declarations are
+/// located textually instead.
+fn find_line(src: &str, keyword: &str, ident: &str) -> usize {
+	let src = &blank_comments_and_strings(src);
+
+	for (offset, _) in src.match_indices(keyword) {
+		if src[..offset].ends_with(is_ident_char) {
+			continue;
+		}
+
+		let rest = &src[offset + keyword.len()..];
+		let name = rest.trim_start();
+
+		if name.len() == rest.len()
+			|| !name.starts_with(ident)
+			|| name[ident.len()..].starts_with(is_ident_char)
+		{
+			continue;
+		}
+
+		return src[..offset].matches('\n').count() + 1;
+	}
+
+	1
+}
+
+/// Formats `path` relative to the crate being compiled, for use in diagnostics.
+pub(crate) fn display_path(path: &Path) -> String {
+	let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+
+	path.strip_prefix(manifest_dir)
+		.unwrap_or(path)
+		.to_string_lossy()
+		.into_owned()
+}
+
+/// Whether `file`, in the directory made of `dir`, keeps its child modules in its own directory,
+/// like `lib.rs`, `main.rs`, `mod.rs` and the crate roots of integration tests, examples,
+/// benchmarks and binaries.
+fn is_mod_rs(file: &Path, dir: &[Component]) -> bool {
+	let stem = file.file_stem().and_then(OsStr::to_str);
+	let dir = dir
+		.last()
+		.and_then(|component| component.as_os_str().to_str());
+
+	matches!(stem, Some("lib" | "main" | "mod"))
+		|| matches!(dir, Some("tests" | "examples" | "benches" | "bin"))
+}
+
+/// The directory holding the child modules of the module defined by `file`, with `.` and `..`
+/// components resolved.
+fn module_dir(file: &Path) -> Vec<Component<'_>> {
+	let mut components: Vec<Component> = vec![];
+
+	for component in file.parent().unwrap_or(Path::new("")).components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				components.pop();
+			}
+			_ => components.push(component),
+		}
+	}
+
+	if !is_mod_rs(file, &components) {
+		components.push(Component::Normal(file.file_stem().unwrap_or_default()));
+	}
+
+	components
+}
+
+/// Infers the path of the module defined by `file` relative to the module defined by `call_site`,
+/// from the standard file layout of Rust modules.
+pub(crate) fn module_path(call_site: &Path, file: &Path) -> Vec<String> {
+	let from = module_dir(call_site);
+	let to = module_dir(file);
+	let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+	let mut path = vec!["super".to_owned(); from.len() - common];
+
+	path.extend(
+		to[common..]
+			.iter()
+			.map(|component| component.as_os_str().to_string_lossy().into_owned()),
+	);
+
+	path
+}
+
+/// Infers the path of the module defined by `file` from the root of the crate being compiled, which
+/// is the closest `lib.rs`, or `main.rs` for binaries, among the directories holding `file`, or the
+/// file named after the crate in a directory of integration tests, examples, benchmarks or
+/// binaries. It's unknown when no crate root is found.
+pub(crate) fn crate_module_path(file: &Path) -> Option<Vec<String>> {
+	let bin_name = std::env::var("CARGO_BIN_NAME").ok();
+	let (name, conventional) = match bin_name {
+		Some(name) => (name, "main.rs"),
+		None => (std::env::var("CARGO_CRATE_NAME").ok()?, "lib.rs"),
+	};
+	let named = format!("{}.rs", name);
+	let root = file.ancestors().skip(1).find_map(|dir| {
+		let dir_name = dir.file_name().and_then(OsStr::to_str);
+		let named = matches!(dir_name, Some("tests" | "examples" | "benches" | "bin"))
+			.then(|| dir.join(&named));
+
+		[Some(dir.join(conventional)), named]
+			.into_iter()
+			.flatten()
+			.find(|root| root.is_file())
+	})?;
+
+	Some(module_path(&root, file))
+}
+
+/// Qualifier referring to items of the module at `module`, relative to the enum's module, through
+/// `self` or `super`.
+pub(crate) fn module_qualifier(module: &[String]) -> Vec<Ident> {
+	let mut qualifier = vec![];
+
+	if module.first().is_none_or(|segment| segment != "super") {
+		qualifier.push(format_ident!("self"));
+	}
+
+	qualifier.extend(module.iter().map(|segment| format_ident!("{}", segment)));
+
+	qualifier
+}
+
+/// Directories skipped while walking the scan location, which hold build outputs, version control
+/// data or sources of other crates.
+const EXCLUDED_DIRS: &[&str] = &["target", "vendor", "node_modules"];
+
+/// Whether the directory named `name` is skipped while walking the scan location.
+fn is_excluded_dir(name: &OsStr) -> bool {
+	let name = name.to_string_lossy();
+
+	name.starts_with('.') || EXCLUDED_DIRS.contains(&&*name)
+}
+
+/// Reads and parses every Rust source file under `root`, or the files listed by `options` relative
+/// to it, containing any of `needles`, going through the cache configured by `options` if any. Most
+/// files of a crate contain nothing the scan is looking for, so checking their text first skips
+/// parsing them.
+fn source_files(
+	root: &Path,
+	options: &Options,
+	needles: &[&str],
+) -> syn::Result<Vec<(PathBuf, String, File)>> {
+	let started = Instant::now();
+	let cache_dir = cache::dir(options);
+	let edition = edition::resolve(options.edition.as_deref());
+	let paths: Vec<PathBuf> = match &options.files {
+		Some(files) => files.iter().map(|file| root.join(file)).collect(),
+		// Sorting keeps the variant order independent of the file system.
+		None => WalkDir::new(root)
+			.sort_by_file_name()
+			.into_iter()
+			.filter_entry(|entry| {
+				let excluded = !options.scan_all
+					&& entry.depth() > 0
+					&& entry.file_type().is_dir()
+					&& is_excluded_dir(entry.file_name());
+
+				if excluded {
+					stats::record(|stats| stats.excluded_dirs += 1);
+				}
+
+				!excluded
+			})
+			.filter_map(Result::ok)
+			.map(|entry| entry.into_path())
+			.filter(|path| !path.is_dir() && path.extension() == Some(OsStr::new("rs")))
+			.collect(),
+	};
+	let mut files = vec![];
+
+	stats::record(|stats| stats.walked += paths.len());
+
+	for path in &paths {
+		let src = fs::read_to_string(path).map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!("unable to read file {}: {}", path.to_string_lossy(), err),
+			)
+		})?;
+
+		if !needles.iter().any(|needle| src.contains(needle)) {
+			stats::record(|stats| stats.skipped += 1);
+
+			continue;
+		}
+
+		let hash = stable_hash(&src);
+
+		if let Some(syntax) = cache_dir
+			.as_ref()
+			.and_then(|dir| cache::load(dir, path, hash, edition.as_deref()))
+		{
+			stats::record(|stats| stats.cached += 1);
+			files.push((path.to_owned(), src, syntax));
+			continue;
+		}
+
+		let syntax = edition::parse_source(&src, edition.as_deref()).map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!("unable to parse file {}: {}", path.to_string_lossy(), err),
+			)
+		})?;
+
+		if let Some(dir) = &cache_dir {
+			cache::store(dir, path, hash, edition.as_deref(), &syntax);
+		}
+
+		stats::record(|stats| stats.parsed += 1);
+		files.push((path.to_owned(), src, syntax));
+	}
+
+	stats::record(|stats| stats.elapsed += started.elapsed());
+
+	Ok(files)
+}
+
+/// The enum built by `item` without the enum_builder attribute, and its parameters, if `item` is
+/// an enum with the [macro@crate::enum_builder] attribute or an invocation of
+/// [macro@crate::enum_builder_decl].
+fn built_enum(item: &Item) -> syn::Result<Option<(ItemEnum, Options)>> {
+	let is_named = |path: &syn::Path, name: &str| {
+		path.segments
+			.last()
+			.is_some_and(|segment| segment.ident == name)
+	};
+
+	match item {
+		Item::Enum(item) => {
+			let Some(index) = item
+				.attrs
+				.iter()
+				.position(|attr| is_named(attr.path(), ENUM_BUILDER))
+			else {
+				return Ok(None);
+			};
+
+			let mut item = item.clone();
+			let options = Options::from_attr(&item.attrs.remove(index))?;
+
+			Ok(Some((item, options)))
+		}
+		Item::Macro(item) if is_named(&item.mac.path, ENUM_BUILDER_DECL) => {
+			let Decl { item, options } = item.mac.parse_body()?;
+
+			Ok(Some((item, options)))
+		}
+		_ => Ok(None),
+	}
+}
+
+/// The name of the enum declared by `item`, an enum or an invocation of
+/// [macro@crate::enum_builder_decl], without parsing its parameters.
+fn declared_ident(item: &Item) -> Option<Ident> {
+	match item {
+		Item::Enum(item) => Some(item.ident.clone()),
+		Item::Macro(item) => (|input: ParseStream| {
+			input.call(Attribute::parse_outer)?;
+			input.parse::<Visibility>()?;
+			input.parse::<Token![enum]>()?;
+
+			let ident = input.parse::<Ident>()?;
+
+			input.parse::<TokenStream>()?;
+			Ok(ident)
+		})
+		.parse2(item.mac.tokens.clone())
+		.ok(),
+		_ => None,
+	}
+}
+
+/// Finds the declaration of the enum named `enum_name` built by [macro@crate::enum_builder] or
+/// [macro@crate::enum_builder_decl] under `root`, returning its file, the enum without the
+/// enum_builder attribute, and its parameters.
+pub(crate) fn find_enum(
+	enum_name: &Ident,
+	root: &Path,
+	options: &Options,
+) -> syn::Result<Option<(PathBuf, ItemEnum, Options)>> {
+	for (path, _, syntax) in source_files(root, options, &[ENUM_BUILDER])? {
+		for item in &syntax.items {
+			let built = match built_enum(item) {
+				Ok(built) => built,
+				Err(err) if declared_ident(item).as_ref() == Some(enum_name) => return Err(err),
+				// Other enums are reported by their own expansion, if they're compiled at all, as
+				// their parameters may require features that aren't enabled.
+				Err(_) => continue,
+			};
+
+			if let Some((item, options)) = built
+				&& item.ident == *enum_name
+			{
+				return Ok(Some((path, item, options)));
+			}
+		}
+	}
+
+	Ok(None)
+}
+
+/// Finds the files under `root` invoking the macro named `name` as an item.
+pub(crate) fn find_macro_calls(
+	name: &str,
+	root: &Path,
+	options: &Options,
+) -> syn::Result<Vec<PathBuf>> {
+	let mut files = vec![];
+
+	for (path, _, syntax) in source_files(root, options, &[name])? {
+		let invokes = syntax.items.iter().any(|item| {
+			matches!(item, Item::Macro(item) if item
+				.mac
+				.path
+				.segments
+				.last()
+				.is_some_and(|segment| segment.ident == name))
+		});
+
+		if invokes {
+			files.push(path);
+		}
+	}
+
+	Ok(files)
+}
+
+/// Finds the declaration of the trait named `trait_name` under `root`, returning it along with the
+/// path of its module relative to the module defined by `call_site`.
+pub(crate) fn find_trait(
+	trait_name: &Ident,
+	root: &Path,
+	call_site: &Path,
+	options: &Options,
+) -> syn::Result<Option<(ItemTrait, Vec<String>)>> {
+	for (path, _, syntax) in source_files(root, options, &["trait"])? {
+		for item in syntax.items {
+			if let Item::Trait(item) = item
+				&& item.ident == *trait_name
+			{
+				return Ok(Some((item, module_path(call_site, &path))));
+			}
+		}
+	}
+
+	Ok(None)
+}
+
+/// Names of the types with an `impl trait_name for Type` block in `files`.
+fn implementors(trait_name: &Ident, files: &[(PathBuf, String, File)]) -> Vec<Ident> {
+	let mut implementors = vec![];
+
+	for (_, _, syntax) in files {
+		for item in &syntax.items {
+			let Item::Impl(item) = item else {
+				continue;
+			};
+
+			let Some((_, trait_path, _)) = &item.trait_ else {
+				continue;
+			};
+
+			if trait_path
+				.segments
+				.last()
+				.is_none_or(|segment| segment.ident != *trait_name)
+			{
+				continue;
+			}
+
+			// Blanket impls and impls for foreign types don't name a type declared in the crate.
+			if let Type::Path(ty) = &*item.self_ty
+				&& ty.qself.is_none()
+				&& let Some(segment) = ty.path.segments.last()
+			{
+				implementors.push(segment.ident.clone());
+			}
+		}
+	}
+
+	implementors
+}
+
+/// Collects the names of the identifiers and lifetimes in `tokens`.
+fn used_names(tokens: TokenStream, names: &mut Vec<String>) {
+	let mut lifetime = false;
+
+	for tree in tokens {
+		match &tree {
+			TokenTree::Group(group) => used_names(group.stream(), names),
+			TokenTree::Ident(ident) if lifetime => names.push(format!("'{}", ident)),
+			TokenTree::Ident(ident) => names.push(ident.to_string()),
+			_ => {}
+		}
+
+		lifetime = matches!(tree, TokenTree::Punct(punct) if punct.as_char() == '\'');
+	}
+}
+
+/// The generics a type alias payload depends on, and the arguments it's applied with if some of its
+/// lifetimes are unused. Those are given `'static`, which doesn't change the aliased type, so they
+/// don't end up as unused lifetimes of the enum.
+fn alias_generics(item: &ItemType) -> (Generics, Option<TokenStream>) {
+	let mut used = vec![];
+
+	used_names(item.ty.to_token_stream(), &mut used);
+
+	// Unused type and const parameters are rejected on type aliases already.
+	let is_used = |param: &GenericParam| match param {
+		GenericParam::Lifetime(param) => used.contains(&param.lifetime.to_string()),
+		_ => true,
+	};
+
+	if item.generics.params.iter().all(is_used) {
+		return (item.generics.clone(), None);
+	}
+
+	let arguments = item.generics.params.iter().map(|param| match param {
+		GenericParam::Lifetime(_) if !is_used(param) => quote!('static),
+		GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+		GenericParam::Type(param) => param.ident.to_token_stream(),
+		GenericParam::Const(param) => param.ident.to_token_stream(),
+	});
+	let arguments = quote!(#(#arguments),*);
+	let mut generics = item.generics.clone();
+
+	generics.params = generics.params.into_iter().filter(is_used).collect();
+
+	(generics, Some(arguments))
+}
+
+/// A type registered with [macro@crate::enum_builder_variant] for another enum than the one whose
+/// variants are discovered.
+pub(crate) struct Registration {
+	/// The enum the type is registered for.
+	pub enum_name: Ident,
+	pub ident: Ident,
+	/// Where the type is declared, formatted for diagnostics.
+	pub location: String,
+}
+
+/// Recursively scans the source files under `root` for types registered as variants of `enum_name`,
+/// and types implementing the `impls` trait if provided. `call_site` is the file declaring the
+/// enum, which module paths are resolved relative to. Registrations for other enums found along
+/// the way are returned too.
+pub(crate) fn discover(
+	enum_name: &Ident,
+	root: &Path,
+	call_site: &Path,
+	options: &Options,
+) -> syn::Result<(Vec<Variant>, Vec<Registration>)> {
+	let trait_name = options.impls.as_ref().map(Ident::to_string);
+	let needles: Vec<&str> = [ENUM_BUILDER_VARIANT]
+		.into_iter()
+		.chain(trait_name.as_deref())
+		.collect();
+	let files = source_files(root, options, &needles)?;
+	let implementors = options
+		.impls
+		.as_ref()
+		.map(|trait_name| implementors(trait_name, &files))
+		.unwrap_or_default();
+	let mut variants = vec![];
+	let mut others = vec![];
+
+	for (path, src, syntax) in files {
+		for item in syntax.items {
+			let mut arguments = None;
+			let mut prefix = None;
+			let (keyword, attrs, vis, ident, generics) = match item {
+				Item::Struct(item) => ("struct", item.attrs, item.vis, item.ident, item.generics),
+				Item::Type(item) => {
+					let (generics, alias_arguments) = alias_generics(&item);
+
+					arguments = alias_arguments;
+					("type", item.attrs, item.vis, item.ident, generics)
+				}
+				Item::Enum(item) => ("enum", item.attrs, item.vis, item.ident, item.generics),
+				Item::Union(item) => ("union", item.attrs, item.vis, item.ident, item.generics),
+				// Impl blocks registered for unsupported types are reported by the
+				// enum_builder_variant attribute itself.
+				Item::Impl(item) => match impl_target(&item) {
+					Ok(target) => {
+						arguments = target.arguments;
+						prefix = target.prefix;
+						(
+							"for",
+							item.attrs,
+							Visibility::Inherited,
+							target.ident,
+							item.generics,
+						)
+					}
+					Err(_) => continue,
+				},
+				_ => continue,
+			};
+
+			let (registrations, other_registrations): (Vec<_>, Vec<_>) = VariantArgs::all(&attrs)
+				.into_iter()
+				.partition(|args| args.enum_name == *enum_name);
+
+			for args in other_registrations {
+				others.push(Registration {
+					enum_name: args.enum_name,
+					ident: ident.clone(),
+					location: format!(
+						"{}:{}",
+						display_path(&path),
+						find_line(&src, keyword, &ident.to_string())
+					),
+				});
+			}
+
+			let mut registrations = registrations.into_iter();
+			let args = match registrations.next() {
+				Some(args) => args,
+				// Implementors of the impls trait are discovered through their declaration.
+				None if keyword != "for" && implementors.contains(&ident) => {
+					VariantArgs::new(enum_name.clone())
+				}
+				None => continue,
+			};
+			let line = find_line(&src, keyword, &ident.to_string());
+
+			if registrations.next().is_some() {
+				return Err(syn::Error::new(
+					Span::call_site(),
+					format!(
+						"{} at {}:{} is registered as a variant of {} more than once",
+						ident,
+						display_path(&path),
+						line,
+						enum_name
+					),
+				));
+			}
+
+			variants.push(Variant {
+				name: ident.clone(),
+				line,
+				vis,
+				ident,
+				generics,
+				arguments,
+				prefix,
+				args,
+				module: module_path(call_site, &path),
+				qualifier: vec![],
+				file: path.clone(),
+			});
+		}
+	}
+
+	Ok((variants, others))
+}
+
+/// Finds the types under `root` registered with [macro@crate::enum_builder_variant], for any enum.
+pub(crate) fn registrations(root: &Path, options: &Options) -> syn::Result<Vec<Registration>> {
+	let mut registrations = vec![];
+
+	for (path, src, syntax) in source_files(root, options, &[ENUM_BUILDER_VARIANT])? {
+		for item in &syntax.items {
+			let (keyword, attrs, ident) = match item {
+				Item::Struct(item) => ("struct", &item.attrs, item.ident.clone()),
+				Item::Type(item) => ("type", &item.attrs, item.ident.clone()),
+				Item::Enum(item) => ("enum", &item.attrs, item.ident.clone()),
+				Item::Union(item) => ("union", &item.attrs, item.ident.clone()),
+				Item::Impl(item) => match impl_target(item) {
+					Ok(target) => ("for", &item.attrs, target.ident),
+					Err(_) => continue,
+				},
+				_ => continue,
+			};
+
+			for args in VariantArgs::all(attrs) {
+				registrations.push(Registration {
+					enum_name: args.enum_name,
+					location: format!(
+						"{}:{}",
+						display_path(&path),
+						find_line(&src, keyword, &ident.to_string())
+					),
+					ident: ident.clone(),
+				});
+			}
+		}
+	}
+
+	Ok(registrations)
+}
+
+/// Names of the enums built by [macro@crate::enum_builder] or [macro@crate::enum_builder_decl]
+/// under `root`.
+pub(crate) fn built_enums(root: &Path, options: &Options) -> syn::Result<Vec<Ident>> {
+	let mut built = vec![];
+
+	for (_, _, syntax) in source_files(root, options, &[ENUM_BUILDER])? {
+		for item in &syntax.items {
+			match built_enum(item) {
+				Ok(Some((item, _))) => built.push(item.ident),
+				Ok(None) => {}
+				// Enums whose parameters require features that aren't enabled are still built in
+				// other configurations.
+				Err(_) => built.extend(declared_ident(item)),
+			}
+		}
+	}
+
+	Ok(built)
+}
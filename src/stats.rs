@@ -0,0 +1,108 @@
+use std::{cell::Cell, env, fs, path::PathBuf, time::Duration};
+
+use proc_macro2::Span;
+use syn::Ident;
+
+/// Counters of the scans made while building an enum, reported by the `stats` parameter.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Stats {
+	/// Source files found while walking the scan locations.
+	pub walked: usize,
+	/// Directories skipped while walking, like `target` or hidden ones.
+	pub excluded_dirs: usize,
+	/// Files skipped without parsing because their text contains nothing looked for.
+	pub skipped: usize,
+	/// Files whose syntax tree was loaded from the cache.
+	pub cached: usize,
+	/// Files parsed.
+	pub parsed: usize,
+	/// Registered types discovered, before filtering.
+	pub discovered: usize,
+	/// Discovered types dropped by the `only_pub` parameter.
+	pub only_pub: usize,
+	/// Discovered types dropped by the `include` parameter.
+	pub include: usize,
+	/// Discovered types dropped by the `exclude` parameter.
+	pub exclude: usize,
+	/// Time spent walking, reading and parsing source files.
+	pub elapsed: Duration,
+}
+
+thread_local! {
+	// Expansions run one at a time on the thread of the proc macro, so counting through a
+	// thread-local spares threading the counters through every scan.
+	static STATS: Cell<Stats> = Cell::new(Stats::default());
+}
+
+/// Updates the counters of the current expansion.
+pub(crate) fn record(update: impl FnOnce(&mut Stats)) {
+	STATS.with(|cell| {
+		let mut stats = cell.get();
+
+		update(&mut stats);
+		cell.set(stats);
+	});
+}
+
+/// Returns the counters of the current expansion, resetting them.
+pub(crate) fn take() -> Stats {
+	STATS.take()
+}
+
+/// Writes `stats`, for the enum named `ident`, to `path` relative to the crate root, or to
+/// `OUT_DIR/enum_builder/<ident>.stats` if no path is provided and the crate has a build script, or
+/// to stderr otherwise.
+pub(crate) fn report(ident: &Ident, stats: &Stats, path: Option<&str>) -> syn::Result<()> {
+	let report = format!(
+		"files walked: {}\n\
+		 directories excluded: {}\n\
+		 files skipped without parsing: {}\n\
+		 files loaded from the cache: {}\n\
+		 files parsed: {}\n\
+		 types discovered: {}\n\
+		 types dropped by only_pub: {}\n\
+		 types dropped by include: {}\n\
+		 types dropped by exclude: {}\n\
+		 scan time: {:.3}ms\n",
+		stats.walked,
+		stats.excluded_dirs,
+		stats.skipped,
+		stats.cached,
+		stats.parsed,
+		stats.discovered,
+		stats.only_pub,
+		stats.include,
+		stats.exclude,
+		stats.elapsed.as_secs_f64() * 1000.0
+	);
+	let path = match path {
+		Some(path) => {
+			PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path)
+		}
+		None => {
+			let Some(out_dir) = env::var_os("OUT_DIR") else {
+				eprint!("// enum_builder scan statistics of {}\n{}", ident, report);
+
+				return Ok(());
+			};
+
+			PathBuf::from(out_dir)
+				.join("enum_builder")
+				.join(format!("{}.stats", ident))
+		}
+	};
+
+	path.parent()
+		.map_or(Ok(()), fs::create_dir_all)
+		.and_then(|_| fs::write(&path, report))
+		.map_err(|err| {
+			syn::Error::new(
+				Span::call_site(),
+				format!(
+					"unable to write scan statistics to {}: {}",
+					path.to_string_lossy(),
+					err
+				),
+			)
+		})
+}
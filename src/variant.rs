@@ -0,0 +1,405 @@
+use std::path::PathBuf;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+	Attribute, Expr, Generics, Ident, Item, ItemImpl, LitInt, LitStr, Meta, PathArguments,
+	PathSegment, Token, Type, Visibility, meta::parser, parenthesized, parse::Parser,
+	punctuated::Punctuated, spanned::Spanned, token,
+};
+
+use crate::scan::{display_path, module_qualifier};
+
+pub(crate) const ENUM_BUILDER_VARIANT: &str = "enum_builder_variant";
+
+/// Parameters of [macro@crate::enum_builder_variant] given without a value.
+const FLAGS: &[&str] = &["default", "inventory"];
+
+/// Parameters accepted by [macro@crate::enum_builder_variant].
+pub(crate) struct VariantArgs {
+	/// The enum type the variant is registered for.
+	pub enum_name: Ident,
+	/// Overrides the name of the generated variant.
+	pub name: Option<Ident>,
+	/// Descriptive key/value pairs exposed through the generated `metadata()` accessor.
+	pub meta: Vec<(String, String)>,
+	/// Explicit discriminant of the generated variant.
+	pub discriminant: Option<Expr>,
+	/// Wire id returned by the generated `wire_id()` method, independent of the variant name.
+	pub id: Option<u32>,
+	/// Priority ordering the variant before those with lower ones, in the registry and the
+	/// generated `kinds_by_priority()`.
+	pub priority: Option<i32>,
+	/// Whether the generated `Default` impl constructs this variant.
+	pub default: bool,
+	/// Function constructing the payload for the registry, instead of its `Default` impl.
+	pub constructor: Option<syn::Path>,
+	/// Type held by the variant instead of the registered one, converted from it with `From`.
+	pub store: Option<Type>,
+	/// strum attributes attached to the generated variant.
+	pub strum: Option<TokenStream>,
+	/// Deprecation note attached to the generated variant.
+	pub deprecated: Option<LitStr>,
+	/// Tags selecting the variant for enums built from a subset of the enum's variants.
+	pub tags: Vec<String>,
+	/// Whether the type is also submitted to the enum's `inventory` registrations.
+	pub inventory: bool,
+}
+
+impl VariantArgs {
+	/// Registration for `enum_name` without any optional parameters.
+	pub fn new(enum_name: Ident) -> Self {
+		Self {
+			enum_name,
+			name: None,
+			meta: vec![],
+			discriminant: None,
+			id: None,
+			priority: None,
+			default: false,
+			constructor: None,
+			store: None,
+			strum: None,
+			deprecated: None,
+			tags: vec![],
+			inventory: false,
+		}
+	}
+
+	pub fn parse(tokens: TokenStream) -> syn::Result<Self> {
+		let mut enum_name = None;
+		let mut name = None;
+		let mut first = true;
+		let mut meta = vec![];
+		let mut discriminant = None;
+		let mut id = None;
+		let mut priority = None;
+		let mut default = false;
+		let mut constructor = None;
+		let mut store = None;
+		let mut strum = None;
+		let mut deprecated = None;
+		let mut tags = vec![];
+		let mut inventory = false;
+		let trees: Vec<TokenTree> = tokens.clone().into_iter().collect();
+		let named = trees.windows(2).any(|pair| {
+			matches!(pair, [TokenTree::Ident(ident), TokenTree::Punct(punct)]
+				if ident == "enum" && punct.as_char() == '=')
+		});
+
+		parser(|nested| {
+			// Only a bare first parameter names the enum positionally, unless it's named with
+			// `enum = "..."`, in which case it's a flag.
+			let positional = std::mem::take(&mut first)
+				&& !named && !nested.input.peek(Token![=])
+				&& !nested.input.peek(token::Paren);
+
+			if positional && FLAGS.iter().any(|flag| nested.path.is_ident(flag)) {
+				return Err(nested.error(format!(
+					"{} is taken as the enum type to register for, as the first parameter; provide \
+					 the enum type first or with enum = \"...\"",
+					nested.path.require_ident()?
+				)));
+			}
+
+			if nested.path.is_ident("enum") {
+				if enum_name.is_some() {
+					return Err(nested.error("the enum type to register for is already provided"));
+				}
+
+				enum_name = Some(nested.value()?.parse::<LitStr>()?.parse()?);
+			} else if positional {
+				enum_name = Some(nested.path.require_ident()?.clone());
+			} else if nested.path.is_ident("name") {
+				name = Some(nested.value()?.parse::<LitStr>()?.parse()?);
+			} else if nested.path.is_ident("meta") {
+				nested.parse_nested_meta(|entry| {
+					let key = entry.path.require_ident()?.to_string();
+					let value = entry.value()?.parse::<LitStr>()?.value();
+
+					meta.push((key, value));
+
+					Ok(())
+				})?;
+			} else if nested.path.is_ident("discriminant") {
+				discriminant = Some(nested.value()?.parse()?);
+			} else if nested.path.is_ident("id") {
+				id = Some(nested.value()?.parse::<LitInt>()?.base10_parse()?);
+			} else if nested.path.is_ident("priority") {
+				let value = nested.value()?;
+				let negative = value.parse::<Option<Token![-]>>()?.is_some();
+				let lit = value.parse::<LitInt>()?;
+				let magnitude: i64 = lit.base10_parse()?;
+				let signed = if negative { -magnitude } else { magnitude };
+
+				priority =
+					Some(i32::try_from(signed).map_err(|_| {
+						syn::Error::new(lit.span(), "the priority must fit in an i32")
+					})?);
+			} else if nested.path.is_ident("default") {
+				default = true;
+			} else if nested.path.is_ident("constructor") {
+				constructor = Some(nested.value()?.parse::<LitStr>()?.parse()?);
+			} else if nested.path.is_ident("store") {
+				store = Some(nested.value()?.parse::<LitStr>()?.parse()?);
+			} else if nested.path.is_ident("strum") {
+				let content;
+
+				parenthesized!(content in nested.input);
+				strum = Some(content.parse()?);
+			} else if nested.path.is_ident("deprecated") {
+				deprecated = Some(nested.value()?.parse()?);
+			} else if nested.path.is_ident("tags") {
+				let content;
+
+				parenthesized!(content in nested.input);
+				tags.extend(
+					Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+						.iter()
+						.map(LitStr::value),
+				);
+			} else if nested.path.is_ident("inventory") {
+				if !cfg!(feature = "inventory") {
+					return Err(nested.error(
+						"the inventory parameter requires the inventory feature of enum_builder",
+					));
+				}
+
+				inventory = true;
+			} else {
+				return Err(nested.error("unsupported enum_builder_variant parameter"));
+			}
+
+			Ok(())
+		})
+		.parse2(tokens.clone())?;
+
+		let Some(enum_name) = enum_name else {
+			return Err(syn::Error::new_spanned(
+				tokens,
+				"expected the enum type to register for",
+			));
+		};
+
+		Ok(Self {
+			enum_name,
+			name,
+			meta,
+			discriminant,
+			id,
+			priority,
+			default,
+			constructor,
+			store,
+			strum,
+			deprecated,
+			tags,
+			inventory,
+		})
+	}
+
+	/// Parses every well-formed registration among `attrs`.
+	pub fn all(attrs: &[Attribute]) -> Vec<Self> {
+		attrs
+			.iter()
+			.filter_map(|attr| {
+				let Meta::List(list) = &attr.meta else {
+					return None;
+				};
+
+				if list
+					.path
+					.segments
+					.last()
+					.is_none_or(|segment| segment.ident != ENUM_BUILDER_VARIANT)
+				{
+					return None;
+				}
+
+				// Malformed registrations are reported by the enum_builder_variant attribute itself.
+				Self::parse(list.tokens.clone()).ok()
+			})
+			.collect()
+	}
+}
+
+/// The type an impl block registered with [macro@crate::enum_builder_variant] is for.
+pub(crate) struct ImplTarget {
+	/// Absolute path of the module declaring the type, followed by `::`, if it's named through one.
+	pub prefix: Option<TokenStream>,
+	pub ident: Ident,
+	/// Generic arguments the type is named with.
+	pub arguments: Option<TokenStream>,
+}
+
+/// The type the impl block `item` is for, which must be named by a single identifier, or by an
+/// absolute path as the module it's declared in isn't known.
+pub(crate) fn impl_target(item: &ItemImpl) -> syn::Result<ImplTarget> {
+	let path = match &*item.self_ty {
+		Type::Path(ty) if ty.qself.is_none() => &ty.path,
+		ty => {
+			return Err(syn::Error::new_spanned(
+				ty,
+				"enum_builder_variant can only register impl blocks for named types",
+			));
+		}
+	};
+	let segments: Vec<&PathSegment> = path.segments.iter().collect();
+	let (last, prefix) = segments.split_last().unwrap();
+	let absolute = path.leading_colon.is_some()
+		|| prefix
+			.first()
+			.is_some_and(|segment| segment.ident == "crate");
+
+	if !prefix.is_empty() && !absolute {
+		return Err(syn::Error::new_spanned(
+			path,
+			"types registered through impl blocks must be named by a single identifier or an absolute path",
+		));
+	}
+
+	let leading_colon = &path.leading_colon;
+	let arguments = match &last.arguments {
+		PathArguments::AngleBracketed(arguments) => {
+			let arguments = &arguments.args;
+
+			Some(quote!(#arguments))
+		}
+		_ => None,
+	};
+
+	Ok(ImplTarget {
+		prefix: absolute.then(|| quote!(#leading_colon #(#prefix::)*)),
+		ident: last.ident.clone(),
+		arguments,
+	})
+}
+
+/// The identifier of the type registered by an [macro@crate::enum_builder_variant] attribute on
+/// `item`. Scanning only discovers types and the types of impl blocks, so registrations on other
+/// items are rejected rather than silently ignored.
+pub(crate) fn target_ident(item: &Item) -> syn::Result<Ident> {
+	let span = match item {
+		Item::Struct(item) => return Ok(item.ident.clone()),
+		Item::Type(item) => return Ok(item.ident.clone()),
+		Item::Enum(item) => return Ok(item.ident.clone()),
+		Item::Union(item) => return Ok(item.ident.clone()),
+		Item::Impl(item) => return impl_target(item).map(|target| target.ident),
+		Item::Fn(item) => item.sig.ident.span(),
+		Item::Trait(item) => item.ident.span(),
+		item => item.span(),
+	};
+
+	Err(syn::Error::new(
+		span,
+		"enum_builder_variant can only be applied to structs, enums, unions, type aliases and impl blocks",
+	))
+}
+
+/// A type discovered in the scanned sources that is registered as a variant.
+pub(crate) struct Variant {
+	/// Identifier of the generated variant.
+	pub name: Ident,
+	/// Visibility of the discovered type.
+	pub vis: Visibility,
+	/// Identifier of the discovered type.
+	pub ident: Ident,
+	pub generics: Generics,
+	/// Generic arguments the type is applied with, when they aren't just its parameters, like for
+	/// type aliases ignoring some of them.
+	pub arguments: Option<TokenStream>,
+	/// Absolute path prefix the type is referred to through instead of `qualifier`, for types
+	/// registered through impl blocks naming them that way.
+	pub prefix: Option<TokenStream>,
+	pub args: VariantArgs,
+	/// Path of the declaring module relative to the enum's module, where `super` segments climb
+	/// out of it.
+	pub module: Vec<String>,
+	/// Module path the type is referred to through from the enum's module.
+	pub qualifier: Vec<Ident>,
+	/// The file the type was discovered in.
+	pub file: PathBuf,
+	/// The line the type is declared on.
+	pub line: usize,
+}
+
+impl Variant {
+	/// Path prefix the type is referred to through from the enum's module.
+	fn qualifier(&self) -> TokenStream {
+		let qualifier = &self.qualifier;
+
+		match &self.prefix {
+			Some(prefix) => prefix.clone(),
+			None => quote!(#(#qualifier::)*),
+		}
+	}
+
+	/// The type held by the variant, which is the `store` type it was registered with if any,
+	/// resolved from the module of the registered type unless its path is absolute.
+	pub fn payload(&self) -> TokenStream {
+		let Some(store) = &self.args.store else {
+			return self.registered();
+		};
+
+		let absolute = match store {
+			Type::Path(ty) if ty.qself.is_none() => {
+				ty.path.leading_colon.is_some()
+					|| ty.path.segments.first().is_some_and(|segment| {
+						segment.ident == "crate"
+							|| segment.ident == "self"
+							|| segment.ident == "super"
+					})
+			}
+			_ => true,
+		};
+
+		if absolute {
+			return quote!(#store);
+		}
+
+		let qualifier = self.qualifier();
+
+		quote!(#qualifier #store)
+	}
+
+	/// The type registered as the variant.
+	pub fn registered(&self) -> TokenStream {
+		let qualifier = self.qualifier();
+		let ident = &self.ident;
+
+		if let Some(arguments) = &self.arguments {
+			return quote!(#qualifier #ident<#arguments>);
+		}
+
+		let (_, ty_generics, _) = self.generics.split_for_impl();
+
+		quote!(#qualifier #ident #ty_generics)
+	}
+
+	/// The `#[deprecated]` attribute of the generated variant, if it's deprecated.
+	pub fn deprecated(&self) -> Option<TokenStream> {
+		self.args
+			.deprecated
+			.as_ref()
+			.map(|note| quote!(#[deprecated(note = #note)]))
+	}
+
+	/// Where the type was discovered, formatted for diagnostics.
+	pub fn location(&self) -> String {
+		format!("{}:{}", display_path(&self.file), self.line)
+	}
+
+	/// Name of the module the type was declared in, unless it is the enum's module or an ancestor.
+	pub fn module_name(&self) -> Option<&str> {
+		self.module
+			.last()
+			.map(String::as_str)
+			.filter(|segment| *segment != "super")
+	}
+
+	/// Qualifier referring to the type through its declaring module, relative to the enum's
+	/// module.
+	pub fn module_qualifier(&self) -> Vec<Ident> {
+		module_qualifier(&self.module)
+	}
+}
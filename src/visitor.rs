@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemEnum;
+
+use crate::{
+	case::to_snake_case,
+	fields::{destructure, matched_self},
+};
+
+/// Generates the visitor trait with one method per variant, and the `accept()` method calling the
+/// method of the current variant.
+pub(crate) fn visitor(item: &ItemEnum) -> TokenStream {
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let visitor_ident = format_ident!("{}Visitor", ident);
+	let generics = &item.generics;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let doc = format!("Visits the payload of every [`{}`] variant.", ident);
+	let mut methods = vec![];
+	let mut arms = vec![];
+
+	for variant in &item.variants {
+		let variant_ident = &variant.ident;
+		let method = format_ident!("visit_{}", to_snake_case(&variant_ident.to_string()));
+		let method_doc = format!("Visits the `{}` variant.", variant_ident);
+		let (pattern, bindings) = destructure(&variant.fields);
+		let idents: Vec<_> = bindings.iter().map(|(ident, _)| ident).collect();
+		let types = bindings.iter().map(|(_, ty)| ty);
+
+		methods.push(quote! {
+			#[doc = #method_doc]
+			fn #method(&mut self, #(#idents: &#types),*);
+		});
+		arms.push(quote!(Self::#variant_ident #pattern => visitor.#method(#(#idents),*)));
+	}
+
+	let matched = matched_self(arms.is_empty());
+
+	quote! {
+		#[doc = #doc]
+		#vis trait #visitor_ident #generics #where_clause {
+			#(#methods)*
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Calls the method of `visitor` visiting this variant.
+			pub fn accept<__V: #visitor_ident #ty_generics>(&self, visitor: &mut __V) {
+				match #matched {
+					#(#arms,)*
+				}
+			}
+		}
+	}
+}
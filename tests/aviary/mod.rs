@@ -0,0 +1,13 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(vis = "pub(crate)")]
+enum Bird {}
+
+#[enum_builder]
+pub enum Insect {}
+
+#[enum_builder_variant(Bird)]
+pub struct Parrot;
+
+#[enum_builder_variant(Insect)]
+pub struct Beetle;
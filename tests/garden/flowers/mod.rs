@@ -0,0 +1,6 @@
+use enum_builder::enum_builder_variant;
+
+pub mod rose;
+
+#[enum_builder_variant(Plant)]
+pub struct Tulip;
@@ -0,0 +1,4 @@
+use enum_builder::enum_builder_variant;
+
+#[enum_builder_variant(Plant)]
+pub struct Rose;
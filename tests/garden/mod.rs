@@ -0,0 +1,10 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+pub mod flowers;
+pub mod trees;
+
+#[enum_builder(qualified_paths, reexport)]
+pub enum Plant {}
+
+#[enum_builder_variant(Plant)]
+pub struct Fern;
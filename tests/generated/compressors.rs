@@ -0,0 +1,7 @@
+#[enum_builder_variant(Compressor)]
+#[derive(Debug, PartialEq)]
+pub struct Zstd;
+
+#[enum_builder_variant(Compressor)]
+#[derive(Debug, PartialEq)]
+pub struct Brotli;
@@ -0,0 +1,10 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+pub mod bar;
+pub mod foo;
+
+#[enum_builder(disambiguate)]
+pub enum Route {}
+
+#[enum_builder_variant(Route)]
+pub struct Logger;
@@ -0,0 +1,25 @@
+use enum_builder::enum_builder_variant;
+
+#[enum_builder_variant(Specimen)]
+#[derive(Debug)]
+pub struct Larva {
+	pub days: u32,
+}
+
+#[enum_builder_variant(Specimen, store = "Chick")]
+pub struct Hatchling;
+
+#[derive(Debug)]
+pub struct Chick {
+	pub weight: u32,
+}
+
+impl From<Hatchling> for Chick {
+	fn from(_: Hatchling) -> Self {
+		Self { weight: 40 }
+	}
+}
+
+#[enum_builder_variant(Brood)]
+#[derive(Debug)]
+pub struct Nestling;
@@ -5,7 +5,7 @@ use crate::AnimalSound;
 #[enum_builder_variant(Animal)]
 pub struct Goat(pub usize);
 
-impl<'a> AnimalSound for Goat {
+impl AnimalSound for Goat {
 	fn speak(&self) {
 		println!("The goat goes{}", " bleat!".repeat(self.0));
 	}
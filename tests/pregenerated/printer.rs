@@ -0,0 +1,90 @@
+// @generated by enum_builder from Printer. Set ENUM_BUILDER_UPDATE_PREGENERATED=1 to regenerate.
+
+#[derive(Debug, PartialEq)]
+pub enum Printer {
+	Laser(Laser),
+	Inkjet(Inkjet),
+}
+#[doc = "The kinds of [`Printer`] variants, without their fields."]
+#[derive(
+	:: core :: clone :: Clone,
+	:: core :: marker :: Copy,
+	:: core :: fmt
+:: Debug,
+	:: core :: cmp :: PartialEq,
+	:: core :: cmp :: Eq,
+	:: core :: hash
+:: Hash,
+)]
+pub enum PrinterKind {
+	Laser,
+	Inkjet,
+}
+impl PrinterKind {
+	#[doc = r" Every kind, in declaration order."]
+	pub const ALL: [Self; 2usize] = [Self::Laser, Self::Inkjet];
+	#[doc = r" Returns an iterator over every kind, in declaration order."]
+	pub fn iter() -> impl ::core::iter::Iterator<Item = Self> {
+		::core::iter::IntoIterator::into_iter(Self::ALL)
+	}
+}
+impl Printer {
+	#[doc = r" Returns the kind of this variant."]
+	pub fn kind(&self) -> PrinterKind {
+		match self {
+			Self::Laser { .. } => PrinterKind::Laser,
+			Self::Inkjet { .. } => PrinterKind::Inkjet,
+		}
+	}
+}
+impl ::core::cmp::Ord for PrinterKind {
+	fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+		let position = |kind: &Self| match *kind {
+			Self::Laser => 0usize,
+			Self::Inkjet => 1usize,
+		};
+		::core::cmp::Ord::cmp(&position(self), &position(other))
+	}
+}
+impl ::core::cmp::PartialOrd for PrinterKind {
+	fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+		::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+	}
+}
+impl ::core::convert::From<&Printer> for PrinterKind {
+	fn from(value: &Printer) -> Self {
+		value.kind()
+	}
+}
+impl ::core::convert::From<Laser> for Printer {
+	fn from(value: Laser) -> Self {
+		Self::Laser(value)
+	}
+}
+impl ::core::convert::From<Inkjet> for Printer {
+	fn from(value: Inkjet) -> Self {
+		Self::Inkjet(value)
+	}
+}
+impl Printer {
+	#[doc = r" The names of every variant, in declaration order."]
+	pub const VARIANT_NAMES: &'static [&'static str] = &["Laser", "Inkjet"];
+	#[doc = r" Returns the name of this variant."]
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Laser { .. } => "Laser",
+			Self::Inkjet { .. } => "Inkjet",
+		}
+	}
+}
+impl PrinterKind {
+	#[doc = r" The names of every variant, in declaration order."]
+	pub const VARIANT_NAMES: &'static [&'static str] = &["Laser", "Inkjet"];
+	#[doc = r" Returns the name of this variant."]
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Laser { .. } => "Laser",
+			Self::Inkjet { .. } => "Inkjet",
+		}
+	}
+}
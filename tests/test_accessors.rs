@@ -0,0 +1,144 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(accessors)]
+enum Pet {
+	Stray,
+}
+
+#[enum_builder_variant(Pet)]
+struct HouseCat;
+
+#[enum_builder_variant(Pet)]
+struct Hamster;
+
+#[test]
+fn test_predicates() {
+	let pet = Pet::HouseCat(HouseCat);
+
+	assert!(pet.is_house_cat());
+	assert!(!pet.is_hamster());
+	assert!(!pet.is_stray());
+	assert!(Pet::Hamster(Hamster).is_hamster());
+	assert!(Pet::Stray.is_stray());
+}
+
+#[enum_builder(accessors)]
+enum Pest {}
+
+#[enum_builder_variant(Pest)]
+struct Mouse {
+	squeaks: u32,
+}
+
+#[enum_builder_variant(Pest)]
+struct Moth;
+
+#[test]
+fn test_payload_accessors() {
+	let mut pest = Pest::Mouse(Mouse { squeaks: 1 });
+
+	assert_eq!(pest.as_mouse().map(|mouse| mouse.squeaks), Some(1));
+	assert!(pest.as_moth().is_none());
+
+	if let Some(mouse) = pest.as_mouse_mut() {
+		mouse.squeaks += 1;
+	}
+
+	let pest = match pest.into_moth() {
+		Ok(_) => panic!("expected a mouse"),
+		Err(pest) => pest,
+	};
+
+	assert_eq!(pest.into_mouse().ok().map(|mouse| mouse.squeaks), Some(2));
+	assert!(Pest::Moth(Moth).as_moth_mut().is_some());
+}
+
+#[enum_builder(from, try_from)]
+enum Critter {}
+
+#[enum_builder_variant(Critter)]
+#[derive(Debug, PartialEq)]
+struct Frog(u8);
+
+#[enum_builder_variant(Critter)]
+#[derive(Debug, PartialEq)]
+struct Newt;
+
+#[test]
+fn test_try_from() {
+	assert_eq!(Frog::try_from(Critter::Frog(Frog(3))).ok(), Some(Frog(3)));
+
+	let Err(critter) = Frog::try_from(Critter::Newt(Newt)) else {
+		panic!("expected a newt");
+	};
+
+	assert_eq!(Newt::try_from(critter).ok(), Some(Newt));
+}
+
+#[test]
+fn test_from() {
+	let critter: Critter = Frog(1).into();
+
+	assert_eq!(Frog::try_from(critter).ok(), Some(Frog(1)));
+	assert!(matches!(Critter::from(Newt), Critter::Newt(Newt)));
+}
+
+#[enum_builder(kind, accessors, names, helpers_doc = "hidden")]
+pub enum Lodger {}
+
+#[enum_builder_variant(Lodger)]
+pub struct Boarder;
+
+#[enum_builder_variant(Lodger)]
+pub struct Tenant;
+
+#[test]
+fn test_hidden_helpers() {
+	let lodger = Lodger::Tenant(Tenant);
+
+	assert!(lodger.is_tenant());
+	assert_eq!(lodger.kind(), LodgerKind::Tenant);
+	assert_eq!(Lodger::VARIANT_NAMES, ["Boarder", "Tenant"]);
+}
+
+#[enum_builder(from, try_from)]
+#[derive(Debug, PartialEq)]
+enum Snapshot {}
+
+#[enum_builder_variant(Snapshot, store = "OwnedCheckpoint")]
+struct Checkpoint<'a> {
+	label: &'a str,
+}
+
+#[derive(Debug, PartialEq)]
+struct OwnedCheckpoint {
+	label: String,
+}
+
+impl<'a> From<Checkpoint<'a>> for OwnedCheckpoint {
+	fn from(checkpoint: Checkpoint<'a>) -> Self {
+		Self {
+			label: checkpoint.label.to_owned(),
+		}
+	}
+}
+
+#[enum_builder_variant(Snapshot)]
+#[derive(Debug, PartialEq)]
+struct Marker;
+
+#[test]
+fn test_store() {
+	let label = String::from("before upgrade");
+	let snapshot = Snapshot::from(Checkpoint { label: &label });
+
+	drop(label);
+
+	assert_eq!(
+		OwnedCheckpoint::try_from(snapshot).ok(),
+		Some(OwnedCheckpoint {
+			label: "before upgrade".to_owned()
+		})
+	);
+	assert_eq!(Snapshot::from(Marker), Snapshot::Marker(Marker));
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(arbitrary)]
+#[derive(Debug, PartialEq)]
+enum Coin {}
+
+#[enum_builder_variant(Coin)]
+#[derive(Debug, PartialEq, Arbitrary)]
+struct Nickel(u8);
+
+#[enum_builder_variant(Coin)]
+#[derive(Debug, PartialEq, Arbitrary)]
+struct Dime {
+	year: u16,
+}
+
+#[test]
+fn test_arbitrary() {
+	let mut seen = [false; 2];
+
+	for first in 0..=255u8 {
+		let bytes = [first, 7, 0, 7];
+		let coin = Coin::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+		match coin {
+			Coin::Nickel(_) => seen[0] = true,
+			Coin::Dime(_) => seen[1] = true,
+		}
+	}
+
+	assert_eq!(seen, [true, true]);
+}
@@ -0,0 +1,56 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(as_ref, as_mut)]
+enum Document<'a> {
+	Blank,
+	Draft { title: &'a str },
+}
+
+#[enum_builder_variant(Document)]
+struct Report {
+	pages: Vec<String>,
+}
+
+fn describe(document: DocumentRef<'_, '_>) -> String {
+	match document {
+		DocumentRef::Blank => "blank".to_owned(),
+		DocumentRef::Draft { title } => format!("draft {}", title),
+		DocumentRef::Report(report) => format!("{} pages", report.pages.len()),
+	}
+}
+
+#[test]
+fn test_as_ref() {
+	let report = Document::Report(Report {
+		pages: vec!["a".to_owned(), "b".to_owned()],
+	});
+	let view = report.as_ref();
+	let copy = view;
+
+	assert_eq!(describe(view), "2 pages");
+	assert_eq!(describe(copy), "2 pages");
+	assert_eq!(
+		describe(Document::Draft { title: "notes" }.as_ref()),
+		"draft notes"
+	);
+	assert_eq!(describe(Document::Blank.as_ref()), "blank");
+}
+
+#[test]
+fn test_as_mut() {
+	let mut report = Document::Report(Report { pages: vec![] });
+
+	if let DocumentMut::Report(report) = report.as_mut() {
+		report.pages.push("intro".to_owned());
+	}
+
+	let mut draft = Document::Draft { title: "old" };
+
+	if let DocumentMut::Draft { title } = draft.as_mut() {
+		*title = "new";
+	}
+
+	assert_eq!(describe(report.as_ref()), "1 pages");
+	assert_eq!(describe(draft.as_ref()), "draft new");
+	assert!(matches!(Document::Blank.as_mut(), DocumentMut::Blank));
+}
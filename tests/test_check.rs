@@ -0,0 +1,10 @@
+use enum_builder::enum_builder_check;
+
+// Every registration under tests/ is for an enum built somewhere under tests/.
+enum_builder_check!();
+
+#[test]
+fn test_check() {
+	// The fixtures are kept out of tests/, which other tests scan for registrations.
+	trybuild::TestCases::new().compile_fail("ui/check_*.rs");
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "clap")]
+
+use clap::Parser;
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(kind, clap)]
+enum Sail {}
+
+#[enum_builder_variant(Sail)]
+struct Jib;
+
+#[enum_builder_variant(Sail)]
+struct TopSail;
+
+#[derive(Parser)]
+struct Rigging {
+	#[arg(long, value_enum)]
+	sail: SailKind,
+}
+
+#[test]
+fn test_clap() {
+	let _ = [Sail::Jib(Jib), Sail::TopSail(TopSail)];
+
+	assert_eq!(
+		Rigging::try_parse_from(["rig", "--sail", "top-sail"])
+			.unwrap()
+			.sail,
+		SailKind::TopSail
+	);
+	assert_eq!(
+		Rigging::try_parse_from(["rig", "--sail", "jib"])
+			.unwrap()
+			.sail,
+		SailKind::Jib
+	);
+	assert!(Rigging::try_parse_from(["rig", "--sail", "spinnaker"]).is_err());
+}
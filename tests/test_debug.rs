@@ -0,0 +1,42 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+pub mod hatchery;
+
+use hatchery::{Chick, Hatchling, Larva, Nestling};
+
+#[enum_builder(debug_impl = "verbose", from, strict_targets)]
+enum Specimen {
+	Fossil { age: u64 },
+}
+
+#[enum_builder_variant(Specimen)]
+#[derive(Debug)]
+pub struct Egg(pub u8);
+
+// Registrations for Specimen, declared outside the scanned directory, aren't unknown targets.
+#[enum_builder(path = "hatchery/", debug_impl = "verbose", strict_targets)]
+enum Brood {}
+
+#[test]
+fn test_verbose_debug() {
+	assert_eq!(
+		format!("{:?}", Specimen::Egg(Egg(2))),
+		"Specimen::Egg (test_debug::Egg) Egg(2)"
+	);
+	assert_eq!(
+		format!("{:?}", Specimen::Larva(Larva { days: 3 })),
+		"Specimen::Larva (test_debug::hatchery::Larva) Larva { days: 3 }"
+	);
+	assert_eq!(
+		format!("{:?}", Specimen::from(Hatchling)),
+		"Specimen::Hatchling (test_debug::hatchery::Chick) Chick { weight: 40 }"
+	);
+	assert_eq!(
+		format!("{:?}", Specimen::Fossil { age: 9000 }),
+		"Specimen::Fossil { age: 9000 }"
+	);
+	assert_eq!(
+		format!("{:?}", Brood::Nestling(Nestling)),
+		"Brood::Nestling (test_debug::hatchery::Nestling) Nestling"
+	);
+}
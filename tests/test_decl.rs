@@ -0,0 +1,36 @@
+use enum_builder::{enum_builder_decl, enum_builder_variant};
+
+enum_builder_decl! {
+	#[derive(Debug, PartialEq)]
+	enum Signal {
+		Hangup,
+	}
+	kind;
+	names;
+}
+
+enum_builder_decl! {
+	#[derive(Debug, PartialEq)]
+	enum ExtendedSignal;
+	extends = "Signal";
+}
+
+#[enum_builder_variant(Signal)]
+#[derive(Debug, PartialEq)]
+struct Interrupt;
+
+#[enum_builder_variant(ExtendedSignal)]
+#[derive(Debug, PartialEq)]
+struct Alarm;
+
+#[test]
+fn test_decl() {
+	assert_eq!(Signal::VARIANT_NAMES, ["Hangup", "Interrupt"]);
+	assert_eq!(Signal::Interrupt(Interrupt).kind(), SignalKind::Interrupt);
+	assert_eq!(
+		ExtendedSignal::from(Signal::Interrupt(Interrupt)),
+		ExtendedSignal::Interrupt(Interrupt)
+	);
+	assert_eq!(ExtendedSignal::from(Signal::Hangup), ExtendedSignal::Hangup);
+	assert_eq!(ExtendedSignal::Alarm(Alarm), ExtendedSignal::Alarm(Alarm));
+}
@@ -0,0 +1,54 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(default, derive(Debug, PartialEq))]
+enum Theme {}
+
+#[enum_builder_variant(Theme)]
+#[derive(Debug, Default, PartialEq)]
+struct Dark;
+
+#[enum_builder_variant(Theme, default)]
+#[derive(Debug, Default, PartialEq)]
+struct Light {
+	contrast: u8,
+}
+
+#[test]
+fn test_default() {
+	assert_eq!(Theme::default(), Theme::Light(Light { contrast: 0 }));
+	assert_ne!(Theme::default(), Theme::Dark(Dark));
+}
+
+#[enum_builder(default, derive(Debug, PartialEq))]
+enum Typeface {}
+
+#[enum_builder_variant(Typeface)]
+#[derive(Debug, Default, PartialEq)]
+struct Serif;
+
+#[enum_builder_variant(default, enum = "Typeface")]
+#[derive(Debug, Default, PartialEq)]
+struct SansSerif;
+
+#[test]
+fn test_default_before_enum() {
+	assert_eq!(Typeface::default(), Typeface::SansSerif(SansSerif));
+	assert_ne!(Typeface::default(), Typeface::Serif(Serif));
+}
+
+#[enum_builder(other = "Custom(String)", metadata)]
+enum Palette {}
+
+#[enum_builder_variant(Palette, meta(hex = "#000000"))]
+struct Black;
+
+#[test]
+fn test_other() {
+	let black = Palette::Black(Black);
+	let custom = Palette::Custom("teal".to_owned());
+
+	assert_eq!(black.metadata().get("hex"), Some("#000000"));
+	assert_eq!(custom.metadata().name, "Custom");
+	assert!(custom.metadata().entries.is_empty());
+	assert!(matches!(custom, Palette::Custom(name) if name == "teal"));
+}
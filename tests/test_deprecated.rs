@@ -0,0 +1,47 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(
+	kind,
+	from_str,
+	from,
+	try_from,
+	accessors,
+	display,
+	names,
+	index,
+	registry,
+	from_name,
+	as_ref,
+	visitor,
+	for_each_variant,
+	match_variant,
+	all_default,
+	metadata,
+	module_path,
+	source_location,
+	type_name,
+	variant_sizes,
+	same_kind,
+	stable_id,
+	derive(Debug, PartialEq)
+)]
+enum Voice {}
+
+#[enum_builder_variant(Voice)]
+#[derive(Debug, Default, PartialEq)]
+struct Opus;
+
+#[enum_builder_variant(Voice, deprecated = "use Opus instead")]
+#[derive(Debug, Default, PartialEq)]
+struct Speex;
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated() {
+	let voice = Voice::Speex(Speex);
+
+	assert_eq!(voice.kind(), VoiceKind::Speex);
+	assert_eq!(voice.to_string(), "Speex");
+	assert_eq!(Voice::VARIANT_NAMES, ["Opus", "Speex"]);
+	assert_eq!(Voice::from(Opus), Voice::Opus(Opus));
+}
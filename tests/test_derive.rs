@@ -0,0 +1,24 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(derive(Debug, Clone, PartialEq))]
+enum Shape {}
+
+#[enum_builder_variant(Shape)]
+#[derive(Debug, Clone, PartialEq)]
+struct Circle {
+	radius: u32,
+}
+
+#[enum_builder_variant(Shape)]
+#[derive(Debug, Clone, PartialEq)]
+struct Square(u32);
+
+#[test]
+fn test_derive() {
+	let circle = Shape::Circle(Circle { radius: 2 });
+	let square = Shape::Square(Square(3));
+
+	assert_eq!(circle.clone(), circle);
+	assert_ne!(circle, square);
+	assert_eq!(format!("{:?}", square), "Square(Square(3))");
+}
@@ -0,0 +1,220 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+trait Instrument {
+	fn play(&self, times: usize) -> String;
+
+	fn tune(&mut self, pitch: u32);
+
+	fn describe<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+		write!(out, "instrument")
+	}
+
+	fn into_pitch(self) -> u32;
+}
+
+#[enum_builder(
+	dispatch = ["Instrument", "Loudness"],
+	must_impl = "Instrument + Loudness + Send + Sync"
+)]
+enum Band {}
+
+trait Loudness {
+	fn decibels(&self) -> u32;
+}
+
+#[enum_builder_variant(Band)]
+struct Drum {
+	pitch: u32,
+}
+
+#[enum_builder_variant(Band)]
+struct Flute {
+	pitch: u32,
+}
+
+impl Instrument for Drum {
+	fn play(&self, times: usize) -> String {
+		"boom ".repeat(times)
+	}
+
+	fn tune(&mut self, pitch: u32) {
+		self.pitch = pitch;
+	}
+
+	fn into_pitch(self) -> u32 {
+		self.pitch
+	}
+}
+
+impl Instrument for Flute {
+	fn play(&self, times: usize) -> String {
+		"toot ".repeat(times)
+	}
+
+	fn tune(&mut self, pitch: u32) {
+		self.pitch = pitch * 2;
+	}
+
+	fn describe<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+		write!(out, "flute")
+	}
+
+	fn into_pitch(self) -> u32 {
+		self.pitch
+	}
+}
+
+impl Loudness for Drum {
+	fn decibels(&self) -> u32 {
+		110
+	}
+}
+
+impl Loudness for Flute {
+	fn decibels(&self) -> u32 {
+		90
+	}
+}
+
+#[test]
+fn test_dispatch() {
+	let mut drum = Band::Drum(Drum { pitch: 0 });
+	let mut flute = Band::Flute(Flute { pitch: 0 });
+	let mut descriptions = String::new();
+
+	drum.tune(3);
+	flute.tune(3);
+	drum.describe(&mut descriptions).unwrap();
+	flute.describe(&mut descriptions).unwrap();
+
+	assert_eq!(drum.play(2), "boom boom ");
+	assert_eq!(flute.play(1), "toot ");
+	assert_eq!(descriptions, "instrumentflute");
+	assert_eq!(drum.decibels() + flute.decibels(), 200);
+	assert_eq!(drum.into_pitch() + flute.into_pitch(), 9);
+}
+
+trait Fetch {
+	async fn fetch(&self, key: &str) -> String;
+}
+
+#[enum_builder(dispatch = "Fetch", assert_send_sync)]
+enum Source {}
+
+trait Render {
+	fn render(&self) -> String;
+
+	fn resize(&mut self, width: u32);
+}
+
+#[enum_builder(dispatch = "Render", dyn_fallback = "Render")]
+enum Widget {}
+
+#[enum_builder_variant(Widget)]
+struct Label {
+	width: u32,
+}
+
+struct External {
+	width: u32,
+}
+
+impl Render for Label {
+	fn render(&self) -> String {
+		format!("label {}", self.width)
+	}
+
+	fn resize(&mut self, width: u32) {
+		self.width = width;
+	}
+}
+
+impl Render for External {
+	fn render(&self) -> String {
+		format!("external {}", self.width)
+	}
+
+	fn resize(&mut self, width: u32) {
+		self.width = width;
+	}
+}
+
+#[test]
+fn test_dyn_fallback() {
+	let mut widgets = [
+		Widget::Label(Label { width: 1 }),
+		Widget::Dynamic(Box::new(External { width: 2 })),
+	];
+
+	widgets[1].resize(5);
+
+	let rendered: Vec<String> = widgets.iter().map(Render::render).collect();
+
+	assert_eq!(rendered, ["label 1", "external 5"]);
+}
+
+#[enum_builder_variant(Source)]
+struct Disk;
+
+#[enum_builder_variant(Source)]
+struct Network;
+
+impl Fetch for Disk {
+	async fn fetch(&self, key: &str) -> String {
+		format!("disk:{}", key)
+	}
+}
+
+impl Fetch for Network {
+	async fn fetch(&self, key: &str) -> String {
+		format!("network:{}", key)
+	}
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+	let mut future = std::pin::pin!(future);
+	let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+
+	loop {
+		if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+			return output;
+		}
+	}
+}
+
+#[test]
+fn test_async_dispatch() {
+	assert_eq!(block_on(Source::Disk(Disk).fetch("a")), "disk:a");
+	assert_eq!(block_on(Source::Network(Network).fetch("b")), "network:b");
+}
+
+fn total_decibels(sources: &[&dyn Loudness]) -> u32 {
+	sources.iter().map(|source| source.decibels()).sum()
+}
+
+#[test]
+fn test_as_dyn() {
+	let band = [
+		Band::Drum(Drum { pitch: 0 }),
+		Band::Flute(Flute { pitch: 0 }),
+	];
+	let sources: Vec<&dyn Loudness> = band.iter().map(Band::as_dyn_loudness).collect();
+
+	assert_eq!(total_decibels(&sources), 200);
+
+	let mut widgets = [
+		Widget::Label(Label { width: 1 }),
+		Widget::Dynamic(Box::new(External { width: 2 })),
+	];
+
+	for widget in &mut widgets {
+		widget.as_dyn_mut().resize(7);
+	}
+
+	let rendered: Vec<String> = widgets
+		.iter()
+		.map(|widget| widget.as_dyn().render())
+		.collect();
+
+	assert_eq!(rendered, ["label 7", "external 7"]);
+}
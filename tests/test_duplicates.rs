@@ -0,0 +1,14 @@
+mod handlers;
+
+use handlers::{Route, bar, foo};
+
+#[test]
+fn test_disambiguate() {
+	let handlers = [
+		Route::FooHandler(foo::Handler),
+		Route::BarHandler(bar::Handler),
+		Route::Logger(handlers::Logger),
+	];
+
+	assert_eq!(handlers.len(), 3);
+}
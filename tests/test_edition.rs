@@ -0,0 +1,12 @@
+use enum_builder::enum_builder;
+
+#[enum_builder(path = "vendor/bronze_age", edition = "2015", names)]
+enum Era {}
+
+struct Bronze;
+
+#[test]
+fn test_edition() {
+	assert_eq!(Era::VARIANT_NAMES, ["Bronze"]);
+	assert!(matches!(Era::Bronze(Bronze), Era::Bronze(_)));
+}
@@ -0,0 +1,34 @@
+use enum_builder::enum_builder;
+
+#[enum_builder(allow_empty, metadata, expect_count = 0)]
+enum Extinct {}
+
+#[enum_builder(
+	allow_empty,
+	derive(Debug, Clone, PartialEq),
+	kind,
+	names,
+	expect_count = 0
+)]
+enum Vanished {}
+
+fn never(extinct: Extinct) -> ! {
+	match extinct {}
+}
+
+fn assert_derives<T: std::fmt::Debug + Clone + PartialEq>() {}
+
+#[test]
+fn test_allow_empty() {
+	let extinct: Option<Extinct> = None;
+
+	assert!(extinct.map(never).is_none());
+}
+
+#[test]
+fn test_allow_empty_derive() {
+	assert_derives::<Vanished>();
+	assert_derives::<VanishedKind>();
+	assert!(VanishedKind::ALL.is_empty());
+	assert!(Vanished::VARIANT_NAMES.is_empty());
+}
@@ -0,0 +1,55 @@
+use std::{error::Error, fmt, num::ParseIntError};
+
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(error, derive(Debug))]
+enum ServiceError {}
+
+#[enum_builder_variant(ServiceError)]
+#[derive(Debug)]
+struct TimeoutError;
+
+impl fmt::Display for TimeoutError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("request timed out")
+	}
+}
+
+impl Error for TimeoutError {}
+
+#[enum_builder_variant(ServiceError)]
+#[derive(Debug)]
+struct PortError(ParseIntError);
+
+impl fmt::Display for PortError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("invalid port")
+	}
+}
+
+impl Error for PortError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.0)
+	}
+}
+
+fn parse_port(port: &str) -> Result<u16, ServiceError> {
+	Ok(port.parse().map_err(PortError)?)
+}
+
+#[test]
+fn test_error() {
+	let timeout = ServiceError::from(TimeoutError);
+
+	assert_eq!(timeout.to_string(), "request timed out");
+	assert!(timeout.source().is_none());
+
+	let error = parse_port("http").unwrap_err();
+
+	assert_eq!(error.to_string(), "invalid port");
+	assert_eq!(
+		error.source().unwrap().to_string(),
+		"invalid digit found in string"
+	);
+	assert_eq!(parse_port("8080").unwrap(), 8080);
+}
@@ -0,0 +1,37 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(derive(Debug, PartialEq))]
+enum CoreCommand {
+	Quit,
+	Jump { height: u32 },
+}
+
+#[enum_builder(extends = "CoreCommand", derive(Debug, PartialEq))]
+enum Command {
+	Pause(u32, bool),
+}
+
+#[enum_builder_variant(CoreCommand)]
+#[derive(Debug, PartialEq)]
+struct Move(i32, i32);
+
+#[enum_builder_variant(Command)]
+#[derive(Debug, PartialEq)]
+struct Fire;
+
+#[test]
+fn test_extends() {
+	assert_eq!(Command::from(CoreCommand::Quit), Command::Quit);
+	assert_eq!(
+		Command::from(CoreCommand::Jump { height: 3 }),
+		Command::Jump { height: 3 }
+	);
+	assert_eq!(
+		Command::from(CoreCommand::Move(Move(1, 2))),
+		Command::Move(Move(1, 2))
+	);
+
+	let commands = [Command::Pause(1, true), Command::Fire(Fire)];
+
+	assert_eq!(commands.len(), 2);
+}
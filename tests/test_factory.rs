@@ -0,0 +1,188 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(all_default)]
+#[derive(Debug, PartialEq)]
+enum Codec {
+	Raw,
+	Tuned { level: u8 },
+}
+
+#[enum_builder_variant(Codec)]
+#[derive(Debug, Default, PartialEq)]
+struct Gzip {
+	level: u32,
+}
+
+#[enum_builder_variant(Codec)]
+#[derive(Debug, Default, PartialEq)]
+struct Lz4;
+
+#[test]
+fn test_all_default() {
+	let codecs: Vec<Codec> = Codec::all_default().collect();
+
+	assert_eq!(
+		codecs,
+		[
+			Codec::Raw,
+			Codec::Tuned { level: 0 },
+			Codec::Gzip(Gzip { level: 0 }),
+			Codec::Lz4(Lz4),
+		]
+	);
+}
+
+mod transports;
+
+use transports::{Quic, Serial, TRANSPORT_REGISTRY, Transport};
+
+#[enum_builder_variant(Transport)]
+#[derive(Debug, Default, PartialEq)]
+struct Tcp;
+
+#[test]
+fn test_registry() {
+	let names: Vec<&str> = TRANSPORT_REGISTRY
+		.iter()
+		.map(|descriptor| descriptor.name)
+		.collect();
+	let modules: Vec<&str> = TRANSPORT_REGISTRY
+		.iter()
+		.map(|descriptor| descriptor.module_path)
+		.collect();
+	let values: Vec<Option<Transport>> = TRANSPORT_REGISTRY
+		.iter()
+		.map(|descriptor| descriptor.constructor.map(|constructor| constructor()))
+		.collect();
+
+	assert_eq!(names, ["Tcp", "Quic", "Serial"]);
+	assert_eq!(
+		modules,
+		[
+			"test_factory",
+			"test_factory::transports",
+			"test_factory::transports"
+		]
+	);
+	assert_eq!(
+		values,
+		[Some(Transport::Tcp(Tcp)), Some(Transport::Quic(Quic)), None]
+	);
+	assert_eq!(Transport::Serial(Serial(1)), Transport::Serial(Serial(1)));
+}
+
+#[test]
+fn test_from_name() {
+	assert_eq!(Transport::from_name("Quic"), Some(Transport::Quic(Quic)));
+	assert_eq!(Transport::from_name("Tcp"), Some(Transport::Tcp(Tcp)));
+	assert_eq!(Transport::from_name("Serial"), None);
+	assert_eq!(Transport::from_name("Carrier"), None);
+}
+
+#[test]
+fn test_module_path() {
+	assert_eq!(Transport::Tcp(Tcp).module_path(), "test_factory");
+	assert_eq!(
+		Transport::Quic(Quic).module_path(),
+		"test_factory::transports"
+	);
+}
+
+struct SinkConfig {
+	dir: &'static str,
+}
+
+#[enum_builder(registry, from_name, config = "SinkConfig")]
+#[derive(Debug, PartialEq)]
+enum Sink {}
+
+#[enum_builder_variant(Sink, constructor = "FileSink::from_config")]
+#[derive(Debug, PartialEq)]
+struct FileSink {
+	path: String,
+}
+
+impl FileSink {
+	fn from_config(config: &SinkConfig) -> Self {
+		Self {
+			path: format!("{}/sink.log", config.dir),
+		}
+	}
+}
+
+#[enum_builder_variant(Sink)]
+#[derive(Debug, Default, PartialEq)]
+struct NullSink;
+
+#[test]
+fn test_constructor() {
+	let config = SinkConfig { dir: "/var/log" };
+
+	assert_eq!(
+		Sink::from_name("FileSink", &config),
+		Some(Sink::FileSink(FileSink {
+			path: "/var/log/sink.log".to_owned()
+		}))
+	);
+	assert_eq!(
+		Sink::from_name("NullSink", &config),
+		Some(Sink::NullSink(NullSink))
+	);
+}
+
+#[enum_builder(kind, registry)]
+#[derive(Debug, PartialEq)]
+enum Middleware {
+	Tracing,
+}
+
+#[enum_builder_variant(Middleware, priority = -5)]
+#[derive(Debug, Default, PartialEq)]
+struct Compress;
+
+#[enum_builder_variant(Middleware, priority = 10)]
+#[derive(Debug, Default, PartialEq)]
+struct Auth;
+
+#[enum_builder_variant(Middleware)]
+#[derive(Debug, Default, PartialEq)]
+struct Logging;
+
+#[enum_builder_variant(Middleware, priority = 10)]
+#[derive(Debug, Default, PartialEq)]
+struct RateLimit;
+
+#[enum_builder_variant(Middleware, priority = -2147483648)]
+#[derive(Debug, Default, PartialEq)]
+struct Recover;
+
+#[test]
+fn test_priority() {
+	let names: Vec<(&str, i32)> = MIDDLEWARE_REGISTRY
+		.iter()
+		.map(|descriptor| (descriptor.name, descriptor.priority))
+		.collect();
+
+	assert_eq!(
+		names,
+		[
+			("Auth", 10),
+			("RateLimit", 10),
+			("Logging", 0),
+			("Compress", -5),
+			("Recover", i32::MIN)
+		]
+	);
+	assert_eq!(
+		Middleware::kinds_by_priority(),
+		[
+			MiddlewareKind::Auth,
+			MiddlewareKind::RateLimit,
+			MiddlewareKind::Tracing,
+			MiddlewareKind::Logging,
+			MiddlewareKind::Compress,
+			MiddlewareKind::Recover,
+		]
+	);
+	assert_eq!(Middleware::Tracing.kind(), MiddlewareKind::Tracing);
+}
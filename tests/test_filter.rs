@@ -0,0 +1,36 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(include = "Backend$", exclude = "^Mock", names)]
+enum Storage {}
+
+#[enum_builder(include = ["^Mock", "^Memory"], names)]
+enum TestStorage {}
+
+#[enum_builder_variant(Storage)]
+#[enum_builder_variant(TestStorage)]
+struct DiskBackend;
+
+#[enum_builder_variant(Storage)]
+#[enum_builder_variant(TestStorage)]
+struct MemoryBackend;
+
+#[enum_builder_variant(Storage)]
+#[enum_builder_variant(TestStorage)]
+struct MockBackend;
+
+#[enum_builder_variant(Storage)]
+#[enum_builder_variant(TestStorage)]
+#[allow(dead_code)]
+struct BackendConfig;
+
+#[test]
+fn test_filter() {
+	assert_eq!(Storage::VARIANT_NAMES, ["DiskBackend", "MemoryBackend"]);
+	assert_eq!(TestStorage::VARIANT_NAMES, ["MemoryBackend", "MockBackend"]);
+	assert_eq!(Storage::DiskBackend(DiskBackend).name(), "DiskBackend");
+	assert_eq!(TestStorage::MockBackend(MockBackend).name(), "MockBackend");
+	assert_eq!(
+		Storage::MemoryBackend(MemoryBackend).name(),
+		"MemoryBackend"
+	);
+}
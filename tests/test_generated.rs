@@ -0,0 +1,60 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(path_env = "CARGO_MANIFEST_DIR", path = "tests/generated", names)]
+#[derive(Debug, PartialEq)]
+enum Compressor {}
+
+include!(concat!(
+	env!("CARGO_MANIFEST_DIR"),
+	"/tests/generated/compressors.rs"
+));
+
+#[test]
+fn test_path_env() {
+	assert_eq!(Compressor::VARIANT_NAMES, ["Zstd", "Brotli"]);
+	assert_eq!(Compressor::Brotli(Brotli).name(), "Brotli");
+	assert_ne!(Compressor::Zstd(Zstd), Compressor::Brotli(Brotli));
+}
+
+#[enum_builder(cache = "target/enum_builder_test_cache", names)]
+enum Archive {}
+
+#[enum_builder_variant(Archive)]
+struct Tarball;
+
+#[enum_builder(cache = "target/enum_builder_test_cache", names)]
+enum Bundle {}
+
+#[enum_builder_variant(Bundle)]
+struct Zipfile;
+
+// Loaded from the cache populated while scanning for Archive.
+struct Cabinet;
+
+#[enum_builder_variant(Bundle)]
+impl Cabinet {}
+
+#[test]
+fn test_cache() {
+	assert_eq!(Archive::VARIANT_NAMES, ["Tarball"]);
+	assert_eq!(Bundle::VARIANT_NAMES, ["Zipfile", "Cabinet"]);
+	assert_eq!(Bundle::Zipfile(Zipfile).name(), "Zipfile");
+	assert!(matches!(Archive::Tarball(Tarball), Archive::Tarball(_)));
+	assert!(matches!(Bundle::Cabinet(Cabinet), Bundle::Cabinet(_)));
+}
+
+#[enum_builder(files = ["test_generated.rs"], names)]
+enum Checksum {}
+
+#[enum_builder_variant(Checksum)]
+struct Crc32;
+
+#[enum_builder_variant(Checksum)]
+struct Adler32;
+
+#[test]
+fn test_files() {
+	assert_eq!(Checksum::VARIANT_NAMES, ["Crc32", "Adler32"]);
+	assert!(matches!(Checksum::Adler32(Adler32), Checksum::Adler32(_)));
+	assert!(matches!(Checksum::Crc32(Crc32), Checksum::Crc32(_)));
+}
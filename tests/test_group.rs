@@ -0,0 +1,61 @@
+mod zoo;
+
+use enum_builder::enum_builder;
+use zoo::{AquaticCreature, BirdsCreature, Creature, Lion, aquatic, birds};
+
+#[enum_builder(source_location)]
+enum Enclosure {
+	Vacant,
+}
+
+#[test]
+fn test_group_by_module() {
+	let lion = Creature::Lion(Lion);
+	let shark: Creature = aquatic::Shark.into();
+	let fish: AquaticCreature = aquatic::Fish.into();
+	let fish: Creature = fish.into();
+	let eagle: Creature = BirdsCreature::Eagle(birds::Eagle).into();
+
+	assert_eq!(lion, Creature::Lion(Lion));
+	assert_eq!(
+		shark,
+		Creature::Aquatic(AquaticCreature::Shark(aquatic::Shark))
+	);
+	assert_eq!(
+		fish,
+		Creature::Aquatic(AquaticCreature::Fish(aquatic::Fish))
+	);
+	assert_eq!(eagle, Creature::Birds(BirdsCreature::Eagle(birds::Eagle)));
+}
+
+#[test]
+fn test_module_path() {
+	assert_eq!(Creature::Lion(Lion).module_path(), "test_group::zoo");
+	assert_eq!(
+		Creature::from(aquatic::Shark).module_path(),
+		"test_group::zoo::aquatic"
+	);
+	assert_eq!(
+		BirdsCreature::Eagle(birds::Eagle).module_path(),
+		"test_group::zoo::birds"
+	);
+}
+
+#[test]
+fn test_source_location() {
+	assert_eq!(Creature::Lion(Lion).source_location().0, "tests/zoo/mod.rs");
+	assert_eq!(
+		Creature::from(aquatic::Shark).source_location(),
+		("tests/zoo/aquatic.rs", 10)
+	);
+	assert_eq!(Enclosure::Vacant.source_location().0, "tests/test_group.rs");
+}
+
+#[test]
+fn test_type_name() {
+	assert_eq!(Creature::Lion(Lion).type_name(), "test_group::zoo::Lion");
+	assert_eq!(
+		Creature::from(aquatic::Shark).type_name(),
+		"test_group::zoo::aquatic::Shark"
+	);
+}
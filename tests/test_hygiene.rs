@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use enum_builder::{enum_builder, enum_builder_variant};
+
+// Local items shadowing the prelude, which generated code must not pick up.
+struct Option;
+struct Result;
+struct From;
+struct TryFrom;
+struct Default;
+struct Iterator;
+struct Clone;
+struct Copy;
+struct Debug;
+struct PartialEq;
+struct Eq;
+struct Hash;
+
+mod core {}
+
+#[enum_builder(
+	accessors,
+	from,
+	try_from,
+	kind,
+	from_str,
+	same_kind,
+	display,
+	names,
+	registry,
+	from_name,
+	all_default,
+	metadata,
+	as_ref,
+	module_path,
+	type_name
+)]
+enum Gadget {}
+
+#[enum_builder_variant(Gadget, default)]
+#[derive(::core::default::Default)]
+struct Lever;
+
+#[enum_builder_variant(Gadget)]
+#[derive(::core::default::Default)]
+struct Pulley;
+
+#[test]
+fn test_hygiene() {
+	let gadget: Gadget = Pulley.into();
+
+	assert!(gadget.is_pulley());
+	assert_eq!(gadget.to_string(), "Pulley");
+	assert_eq!("Lever".parse::<GadgetKind>(), Ok(GadgetKind::Lever));
+	assert!(Gadget::from_name("Lever").is_some_and(|gadget| gadget.is_lever()));
+	assert_eq!(Gadget::all_default().count(), 2);
+}
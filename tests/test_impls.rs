@@ -0,0 +1,99 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+trait Greeter {
+	fn greet(&self) -> &'static str;
+}
+
+#[enum_builder(impls = "Greeter", dispatch = "Greeter")]
+enum Greeting {}
+
+struct English;
+
+struct French;
+
+#[enum_builder_variant(Greeting)]
+struct Silent;
+
+struct Unrelated;
+
+impl Greeter for English {
+	fn greet(&self) -> &'static str {
+		"hello"
+	}
+}
+
+impl Greeter for French {
+	fn greet(&self) -> &'static str {
+		"bonjour"
+	}
+}
+
+impl Greeter for Silent {
+	fn greet(&self) -> &'static str {
+		""
+	}
+}
+
+impl<T: Greeter> Greeter for Box<T> {
+	fn greet(&self) -> &'static str {
+		(**self).greet()
+	}
+}
+
+impl Unrelated {
+	fn greet(&self) -> &'static str {
+		"?"
+	}
+}
+
+#[test]
+fn test_impls() {
+	let greetings = [
+		Greeting::English(English),
+		Greeting::French(French),
+		Greeting::Silent(Silent),
+	];
+	let words: Vec<&str> = greetings.iter().map(Greeter::greet).collect();
+
+	assert_eq!(words, ["hello", "bonjour", ""]);
+	assert_eq!(Unrelated.greet(), "?");
+	assert_eq!(Box::new(English).greet(), "hello");
+}
+
+trait Bow {
+	fn strings(&self) -> u8;
+}
+
+#[enum_builder(dispatch = "Bow", derive(Debug, PartialEq))]
+enum Fiddle {}
+
+mod luthier {
+	#[derive(Debug, PartialEq)]
+	pub struct Viola;
+
+	#[derive(Debug, PartialEq)]
+	pub struct Rebec<const STRINGS: u8>;
+}
+
+use luthier::Viola;
+
+#[enum_builder_variant(Fiddle)]
+impl Bow for Viola {
+	fn strings(&self) -> u8 {
+		4
+	}
+}
+
+#[enum_builder_variant(Fiddle)]
+impl Bow for crate::luthier::Rebec<3> {
+	fn strings(&self) -> u8 {
+		3
+	}
+}
+
+#[test]
+fn test_impl_registration() {
+	let fiddles = [Fiddle::Viola(Viola), Fiddle::Rebec(luthier::Rebec)];
+
+	assert_eq!(fiddles.map(|fiddle| fiddle.strings()), [4, 3]);
+}
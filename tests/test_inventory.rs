@@ -0,0 +1,36 @@
+#![cfg(feature = "inventory")]
+
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(inventory)]
+pub enum Courier {}
+
+#[enum_builder_variant(Courier, inventory, meta(region = "eu"))]
+pub struct Parcel;
+
+#[enum_builder_variant(Courier, inventory)]
+pub struct Drone;
+
+#[test]
+fn test_inventory() {
+	let _ = [Courier::Parcel(Parcel), Courier::Drone(Drone)];
+	let mut registrations: Vec<_> = CourierRegistration::iter()
+		.map(|registration| {
+			(
+				registration.type_name,
+				registration.module_path,
+				registration.meta,
+			)
+		})
+		.collect();
+
+	registrations.sort();
+
+	assert_eq!(
+		registrations,
+		[
+			("Drone", "test_inventory", &[][..]),
+			("Parcel", "test_inventory", &[("region", "eu")][..]),
+		]
+	);
+}
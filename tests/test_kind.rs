@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, HashSet};
+
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(kind, repr = "u8", type_ids)]
+enum Vehicle {
+	Bicycle = 3,
+}
+
+#[enum_builder(kind = "FuelType", other)]
+enum Fuel {}
+
+#[enum_builder_variant(Vehicle, discriminant = 5)]
+#[enum_builder_variant(Fuel)]
+struct Car {
+	#[allow(dead_code)]
+	seats: u8,
+}
+
+#[enum_builder_variant(Vehicle, discriminant = 9)]
+struct Truck;
+
+#[test]
+fn test_kind() {
+	let car = Vehicle::Car(Car { seats: 4 });
+	let kinds = HashSet::from([car.kind(), VehicleKind::from(&Vehicle::Truck(Truck))]);
+
+	assert_eq!(car.kind(), VehicleKind::Car);
+	assert_eq!(Vehicle::Bicycle.kind(), VehicleKind::Bicycle);
+	assert_eq!(kinds.len(), 2);
+	assert_eq!(VehicleKind::Bicycle as u8, 3);
+	assert_eq!(VehicleKind::Truck as u8, 9);
+	assert_eq!(Fuel::Other.kind(), FuelType::Other);
+	assert_eq!(Fuel::Car(Car { seats: 2 }).kind(), FuelType::Car);
+}
+
+#[test]
+fn test_kind_ord() {
+	let mut kinds = vec![VehicleKind::Truck, VehicleKind::Bicycle, VehicleKind::Car];
+
+	kinds.sort();
+
+	assert_eq!(
+		kinds,
+		[VehicleKind::Bicycle, VehicleKind::Car, VehicleKind::Truck]
+	);
+	assert!(FuelType::Car < FuelType::Other);
+
+	let counts = BTreeMap::from([(VehicleKind::Truck, 1), (VehicleKind::Car, 2)]);
+
+	assert_eq!(counts.keys().next(), Some(&VehicleKind::Car));
+}
+
+#[test]
+fn test_kind_iter() {
+	assert_eq!(
+		VehicleKind::ALL,
+		[VehicleKind::Bicycle, VehicleKind::Car, VehicleKind::Truck]
+	);
+	assert_eq!(
+		FuelType::iter().collect::<Vec<_>>(),
+		[FuelType::Car, FuelType::Other]
+	);
+}
+
+#[test]
+fn test_type_ids() {
+	assert_eq!(Vehicle::kind_of::<Truck>(), Some(VehicleKind::Truck));
+	assert_eq!(Vehicle::kind_of::<String>(), None);
+	assert!(Vehicle::Truck(Truck).holds::<Truck>());
+	assert!(!Vehicle::Truck(Truck).holds::<Car>());
+	assert!(!Vehicle::Bicycle.holds::<Truck>());
+	assert_eq!(Vehicle::type_ids().len(), 2);
+}
+
+#[enum_builder(names, kind)]
+enum Engine {
+	Electric,
+}
+
+#[enum_builder_variant(Engine)]
+struct Diesel;
+
+#[test]
+fn test_names() {
+	assert_eq!(Engine::VARIANT_NAMES, ["Electric", "Diesel"]);
+	assert_eq!(Engine::Diesel(Diesel).name(), "Diesel");
+	assert_eq!(EngineKind::VARIANT_NAMES, Engine::VARIANT_NAMES);
+	assert_eq!(EngineKind::Electric.name(), "Electric");
+	assert_eq!(Engine::Electric.name(), "Electric");
+}
+
+#[enum_builder(index, kind)]
+enum Wheel {
+	Spare,
+}
+
+#[enum_builder_variant(Wheel)]
+struct Front;
+
+#[enum_builder_variant(Wheel)]
+struct Rear;
+
+#[test]
+fn test_index() {
+	let mut counts = [0; Wheel::COUNT];
+
+	for wheel in [Wheel::Front(Front), Wheel::Rear(Rear), Wheel::Rear(Rear)] {
+		counts[wheel.variant_index()] += 1;
+	}
+
+	assert_eq!(counts, [0, 1, 2]);
+	assert_eq!(Wheel::Spare.variant_index(), 0);
+	assert_eq!(WheelKind::COUNT, 3);
+	assert_eq!(WheelKind::Rear.variant_index(), 2);
+}
+
+#[enum_builder(kind, from_str)]
+enum Gear {}
+
+#[enum_builder(kind, from_str, case_insensitive)]
+enum Brake {}
+
+#[enum_builder_variant(Gear)]
+#[enum_builder_variant(Brake)]
+struct Low;
+
+#[enum_builder_variant(Gear)]
+#[enum_builder_variant(Brake)]
+struct High;
+
+#[test]
+fn test_from_str() {
+	assert_eq!("Low".parse(), Ok(GearKind::Low));
+	assert_eq!(GearKind::from_name("High"), Some(GearKind::High));
+	assert_eq!(GearKind::from_name("high"), None);
+	assert_eq!("Medium".parse::<GearKind>(), Err(ParseGearKindError));
+	assert_eq!(ParseGearKindError.to_string(), "unknown GearKind name");
+	assert_eq!("hIGh".parse(), Ok(BrakeKind::High));
+	assert_eq!(Gear::Low(Low).kind(), GearKind::Low);
+	assert_eq!(Brake::High(High).kind(), BrakeKind::High);
+}
+
+#[enum_builder(kind, from_str, display, rename_all = "kebab-case")]
+enum Clutch {}
+
+#[enum_builder_variant(Clutch)]
+struct DualPlate;
+
+#[enum_builder_variant(Clutch)]
+struct Centrifugal;
+
+#[test]
+fn test_rename_all() {
+	assert_eq!(Clutch::DualPlate(DualPlate).to_string(), "dual-plate");
+	assert_eq!(ClutchKind::Centrifugal.to_string(), "centrifugal");
+	assert_eq!("dual-plate".parse(), Ok(ClutchKind::DualPlate));
+	assert_eq!(ClutchKind::from_name("DualPlate"), None);
+}
+
+#[enum_builder(display, kind)]
+enum Horn {
+	Silent,
+}
+
+#[enum_builder_variant(Horn)]
+struct Loud(#[allow(dead_code)] u8);
+
+#[test]
+fn test_display() {
+	assert_eq!(Horn::Loud(Loud(9)).to_string(), "Loud");
+	assert_eq!(Horn::Silent.to_string(), "Silent");
+	assert_eq!(format!("{}", HornKind::Loud), "Loud");
+}
+
+#[enum_builder(same_kind)]
+enum Reading {}
+
+#[enum_builder_variant(Reading)]
+struct Celsius(#[allow(dead_code)] f64);
+
+#[enum_builder_variant(Reading)]
+struct Humidity(#[allow(dead_code)] f64);
+
+#[test]
+fn test_same_kind() {
+	use std::collections::HashSet;
+
+	let readings = [
+		Reading::Celsius(Celsius(20.5)),
+		Reading::Celsius(Celsius(21.0)),
+		Reading::Humidity(Humidity(0.4)),
+	];
+
+	assert!(readings[0].same_kind(&readings[1]));
+	assert!(!readings[0].same_kind(&readings[2]));
+
+	let kinds: HashSet<ReadingKindEq> = readings.into_iter().map(ReadingKindEq).collect();
+
+	assert_eq!(kinds.len(), 2);
+}
+
+#[enum_builder(variant_sizes = 8)]
+enum Cargo {
+	Empty,
+}
+
+#[enum_builder_variant(Cargo)]
+struct Crate(#[allow(dead_code)] [u8; 4]);
+
+#[enum_builder_variant(Cargo)]
+struct Pallet(#[allow(dead_code)] [u8; 16]);
+
+#[test]
+fn test_variant_sizes() {
+	let cargo = [
+		Cargo::Empty,
+		Cargo::Crate(Crate([0; 4])),
+		Cargo::Pallet(Pallet([0; 16])),
+	];
+
+	assert_eq!(cargo.len(), 3);
+	assert_eq!(
+		Cargo::VARIANT_SIZES,
+		[("Empty", 0), ("Crate", 4), ("Pallet", 16)]
+	);
+}
+
+#[enum_builder(variant_sizes = 4611686018427387904)]
+enum Freight {}
+
+#[enum_builder_variant(Freight)]
+struct Tote(#[allow(dead_code)] [u8; 4]);
+
+#[enum_builder_variant(Freight)]
+struct Container(#[allow(dead_code)] [u8; 64]);
+
+#[test]
+fn test_variant_sizes_large_ratio() {
+	let freight = [
+		Freight::Tote(Tote([0; 4])),
+		Freight::Container(Container([0; 64])),
+	];
+
+	assert_eq!(freight.len(), 2);
+	assert_eq!(Freight::VARIANT_SIZES, [("Tote", 4), ("Container", 64)]);
+}
@@ -0,0 +1,23 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(lock = "tests/test_lock.lock", names)]
+enum Firmware {}
+
+#[enum_builder_variant(Firmware)]
+struct Bootloader;
+
+#[enum_builder_variant(Firmware)]
+struct Recovery;
+
+#[test]
+fn test_lock() {
+	assert_eq!(Firmware::VARIANT_NAMES, ["Bootloader", "Recovery"]);
+	assert!(matches!(
+		Firmware::Bootloader(Bootloader),
+		Firmware::Bootloader(_)
+	));
+	assert!(matches!(
+		Firmware::Recovery(Recovery),
+		Firmware::Recovery(_)
+	));
+}
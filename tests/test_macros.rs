@@ -0,0 +1,139 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(for_each_variant, assert_variants)]
+enum Sensor {}
+
+assert_sensor_variants!(Barometer, Thermometer);
+
+#[enum_builder_variant(Sensor)]
+struct Thermometer;
+
+#[enum_builder_variant(Sensor)]
+struct Barometer(#[allow(dead_code)] u64);
+
+#[test]
+fn test_for_each_variant() {
+	let _ = Sensor::Thermometer(Thermometer);
+	let mut names = vec![];
+	let mut sizes = vec![];
+
+	macro_rules! collect {
+		($variant:ident, $payload:ty, $names:ident, $sizes:ident) => {
+			$names.push(stringify!($variant));
+			$sizes.push(std::mem::size_of::<$payload>());
+		};
+	}
+
+	sensor_for_each_variant!(collect, names, sizes);
+
+	assert_eq!(names, ["Thermometer", "Barometer"]);
+	assert_eq!(sizes, [0, 8]);
+}
+
+#[enum_builder(match_variant)]
+enum Gauge {}
+
+#[enum_builder_variant(Gauge)]
+struct Dial(u32);
+
+#[enum_builder_variant(Gauge)]
+struct Needle(u32);
+
+#[enum_builder(match_variant)]
+enum Meter {
+	Broken,
+}
+
+#[enum_builder_variant(Meter)]
+struct Analog(u32);
+
+#[test]
+fn test_match_variant() {
+	let gauges = [Gauge::Dial(Dial(1)), Gauge::Needle(Needle(2))];
+	let total: u32 = gauges
+		.iter()
+		.map(|gauge| match_gauge!(gauge, inner => inner.0))
+		.sum();
+
+	assert_eq!(total, 3);
+	assert_eq!(
+		match_meter!(Meter::Analog(Analog(4)), inner => inner.0, _ => 0),
+		4
+	);
+	assert_eq!(match_meter!(Meter::Broken, inner => inner.0, _ => 0), 0);
+}
+
+trait TelemetryProbe {
+	const LABEL: &'static str;
+	const UNIT: &'static str;
+}
+
+macro_rules! telemetry {
+	($enum:ident, $variant:ident, $payload:ty, { $($key:ident = $value:literal),* }) => {
+		impl TelemetryProbe for $payload {
+			const LABEL: &'static str = concat!(stringify!($enum), "::", stringify!($variant));
+			const UNIT: &'static str = telemetry!(@unit $($key = $value),*);
+		}
+	};
+	(@unit unit = $unit:literal) => {
+		$unit
+	};
+	(@unit) => {
+		""
+	};
+}
+
+#[enum_builder(variant_macro = "telemetry")]
+enum Telemetry {}
+
+#[enum_builder_variant(Telemetry, meta(unit = "hPa"))]
+struct Barograph(#[allow(dead_code)] f32);
+
+#[enum_builder_variant(Telemetry)]
+struct Tally(#[allow(dead_code)] u32);
+
+#[test]
+fn test_variant_macro() {
+	let _ = [
+		Telemetry::Barograph(Barograph(1013.0)),
+		Telemetry::Tally(Tally(3)),
+	];
+
+	assert_eq!(Barograph::LABEL, "Telemetry::Barograph");
+	assert_eq!(Barograph::UNIT, "hPa");
+	assert_eq!(Tally::LABEL, "Telemetry::Tally");
+	assert_eq!(Tally::UNIT, "");
+}
+
+#[enum_builder(qualified_paths, mod_name = "anemometry", for_each_variant)]
+enum Anemometer {}
+
+#[enum_builder_variant(Anemometer)]
+struct CupVane(#[allow(dead_code)] u16);
+
+mod dashboard {
+	#[test]
+	fn test_match_variant_from_another_module() {
+		let gauge = super::Gauge::Needle(super::Needle(5));
+
+		assert_eq!(super::match_gauge!(gauge, inner => inner.0), 5);
+	}
+
+	mod readout {
+		#[test]
+		fn test_for_each_variant_from_another_module() {
+			let _ = super::super::Anemometer::CupVane(super::super::CupVane(12));
+			let mut sizes = vec![];
+
+			macro_rules! measure {
+				($variant:ident, $payload:ty, $sizes:ident) => {
+					$sizes.push(std::mem::size_of::<$payload>());
+				};
+			}
+
+			super::super::anemometry::anemometer_for_each_variant!(measure, sizes);
+
+			assert_eq!(sizes, [2]);
+		}
+	}
+}
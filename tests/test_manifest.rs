@@ -0,0 +1,30 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(manifest = "target/enum_builder/Lamp.json")]
+enum Lamp {}
+
+#[enum_builder_variant(Lamp)]
+struct Halogen;
+
+#[enum_builder_variant(Lamp)]
+struct Led<T>(#[allow(dead_code)] T);
+
+#[test]
+fn test_manifest() {
+	let _ = [Lamp::<u8>::Halogen(Halogen), Lamp::Led(Led(1))];
+	let path = concat!(env!("CARGO_MANIFEST_DIR"), "/target/enum_builder/Lamp.json");
+	let manifest = std::fs::read_to_string(path).unwrap();
+
+	assert_eq!(
+		manifest,
+		concat!(
+			"{\n",
+			"\t\"enum\": \"Lamp\",\n",
+			"\t\"variants\": [\n",
+			"\t\t{\"name\": \"Halogen\", \"type\": \"Halogen\", \"file\": \"tests/test_manifest.rs\", \"line\": 7, \"module\": \"self\"},\n",
+			"\t\t{\"name\": \"Led\", \"type\": \"Led<T>\", \"file\": \"tests/test_manifest.rs\", \"line\": 10, \"module\": \"self\"}\n",
+			"\t]\n",
+			"}\n",
+		)
+	);
+}
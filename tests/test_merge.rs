@@ -0,0 +1,111 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(metadata)]
+enum Event {
+	Tick,
+	Shutdown { code: i32 },
+}
+
+#[enum_builder_variant(Event, meta(source = "keyboard"))]
+struct KeyPress(char);
+
+#[test]
+fn test_merge() {
+	let events = [
+		Event::Tick,
+		Event::Shutdown { code: 1 },
+		Event::KeyPress(KeyPress('q')),
+	];
+
+	assert!(matches!(events[1], Event::Shutdown { code: 1 }));
+	assert!(matches!(events[2], Event::KeyPress(KeyPress('q'))));
+	assert_eq!(events[0].metadata().name, "Tick");
+	assert_eq!(events[2].metadata().get("source"), Some("keyboard"));
+	assert_eq!(EVENT_METADATA.len(), 3);
+}
+
+trait Encode {
+	fn encode(&self) -> Vec<u8>;
+}
+
+impl Encode for u8 {
+	fn encode(&self) -> Vec<u8> {
+		vec![*self]
+	}
+}
+
+#[enum_builder]
+enum Frame<T> {}
+
+#[enum_builder_variant(Frame)]
+struct Packet<T>(T)
+where
+	T: Encode;
+
+#[enum_builder_variant(Frame)]
+struct Heartbeat<T>(T)
+where
+	T: Encode + Clone;
+
+impl<T> Frame<T>
+where
+	T: Encode + Clone,
+{
+	fn encode(&self) -> Vec<u8> {
+		match self {
+			Frame::Packet(packet) => packet.0.encode(),
+			Frame::Heartbeat(heartbeat) => heartbeat.0.clone().encode(),
+		}
+	}
+}
+
+#[test]
+fn test_where_clauses() {
+	assert_eq!(Frame::Packet(Packet(7u8)).encode(), [7]);
+	assert_eq!(Frame::Heartbeat(Heartbeat(1u8)).encode(), [1]);
+}
+
+#[enum_builder]
+enum Cell {}
+
+#[enum_builder_variant(Cell)]
+struct Number<T>(T);
+
+#[enum_builder_variant(Cell)]
+struct List<T>(Vec<T>);
+
+#[test]
+fn test_rename_generics() {
+	let cells: [Cell<u8, &str>; 2] = [Cell::Number(Number(1)), Cell::List(List(vec!["a", "b"]))];
+
+	assert!(matches!(&cells[0], Cell::Number(Number(1))));
+	assert!(matches!(&cells[1], Cell::List(List(items)) if items.len() == 2));
+}
+
+struct Reptile<'a, T>(&'a str, T);
+
+struct NoLegs;
+
+#[enum_builder]
+enum Terrarium {}
+
+#[enum_builder_variant(Terrarium)]
+type Viper<'a> = Reptile<'a, NoLegs>;
+
+#[enum_builder_variant(Terrarium)]
+type Gecko<'a, T> = Reptile<'static, T>;
+
+#[test]
+fn test_alias_arguments() {
+	let name = String::from("viper");
+	let terrarium: [Terrarium<'_, u8>; 2] = [
+		Terrarium::Viper(Reptile(&name, NoLegs)),
+		Terrarium::Gecko(Reptile("gecko", 4)),
+	];
+
+	assert!(matches!(
+		terrarium[0],
+		Terrarium::Viper(Reptile("viper", NoLegs))
+	));
+	assert!(matches!(terrarium[1], Terrarium::Gecko(Reptile(_, 4))));
+}
@@ -0,0 +1,25 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(metadata)]
+#[derive(Debug)]
+enum Gemstone {}
+
+#[enum_builder_variant(Gemstone, meta(author = "me", version = "1.2"))]
+#[derive(Debug)]
+struct Ruby {}
+
+#[enum_builder_variant(Gemstone)]
+#[derive(Debug)]
+struct Opal {}
+
+#[test]
+fn test_metadata() {
+	let ruby = Gemstone::Ruby(Ruby {});
+	let opal = Gemstone::Opal(Opal {});
+
+	assert_eq!(ruby.metadata().name, "Ruby");
+	assert_eq!(ruby.metadata().get("author"), Some("me"));
+	assert_eq!(ruby.metadata().get("version"), Some("1.2"));
+	assert_eq!(opal.metadata().get("author"), None);
+	assert_eq!(GEMSTONE_METADATA.len(), 2);
+}
@@ -0,0 +1,18 @@
+mod workshop;
+
+use workshop::{Chisel, Implement, Use, implement_gen, power::Lathe};
+
+#[test]
+fn test_mod_name() {
+	let tools = [Implement::Chisel(Chisel), Implement::Lathe(Lathe)];
+
+	assert_eq!(tools[0].noise(), "tap");
+	assert_eq!(tools[1].noise(), "whirr");
+	assert_eq!(
+		implement_gen::ImplementKind::from(&tools[1]),
+		implement_gen::ImplementKind::Lathe
+	);
+	assert_eq!(tools[1].module_path(), "test_mod_name::workshop::power");
+	assert_eq!(implement_gen::IMPLEMENT_REGISTRY.len(), 2);
+	assert_eq!(workshop::bench_names(), ["Chisel", "Power"]);
+}
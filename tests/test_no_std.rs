@@ -0,0 +1,68 @@
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+use alloc::boxed::Box;
+
+use enum_builder::{enum_builder, enum_builder_variant};
+
+trait Drive {
+	fn irq(&self) -> u8;
+}
+
+#[enum_builder(
+	no_std,
+	dispatch = "Drive",
+	dyn_fallback = "Drive",
+	kind,
+	from_str,
+	same_kind,
+	display,
+	names,
+	registry,
+	type_name
+)]
+enum Driver {}
+
+#[enum_builder_variant(Driver)]
+#[derive(Default)]
+struct Uart;
+
+#[enum_builder_variant(Driver)]
+#[derive(Default)]
+struct Spi;
+
+struct Dma;
+
+impl Drive for Uart {
+	fn irq(&self) -> u8 {
+		4
+	}
+}
+
+impl Drive for Spi {
+	fn irq(&self) -> u8 {
+		5
+	}
+}
+
+impl Drive for Dma {
+	fn irq(&self) -> u8 {
+		9
+	}
+}
+
+#[test]
+fn test_no_std() {
+	let drivers = [
+		Driver::Uart(Uart),
+		Driver::Spi(Spi),
+		Driver::Dynamic(Box::new(Dma)),
+	];
+
+	assert_eq!(drivers.map(|driver| driver.irq()), [4, 5, 9]);
+	assert_eq!("Spi".parse(), Ok(DriverKind::Spi));
+	assert_eq!(Driver::VARIANT_NAMES, ["Uart", "Spi", "Dynamic"]);
+	assert_eq!(DRIVER_REGISTRY.len(), 2);
+}
@@ -0,0 +1,15 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(non_exhaustive, derive(Debug))]
+pub enum Tool {}
+
+#[enum_builder_variant(Tool)]
+#[derive(Debug)]
+pub struct Hammer;
+
+#[test]
+fn test_non_exhaustive() {
+	let tool = Tool::Hammer(Hammer);
+
+	assert_eq!(format!("{:?}", tool), "Hammer(Hammer)");
+}
@@ -0,0 +1,20 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(pregenerated = "tests/pregenerated/printer.rs", kind, from, names)]
+#[derive(Debug, PartialEq)]
+pub enum Printer {}
+
+#[enum_builder_variant(Printer)]
+#[derive(Debug, PartialEq)]
+pub struct Laser;
+
+#[enum_builder_variant(Printer)]
+#[derive(Debug, PartialEq)]
+pub struct Inkjet;
+
+#[test]
+fn test_pregenerated() {
+	assert_eq!(Printer::VARIANT_NAMES, ["Laser", "Inkjet"]);
+	assert_eq!(Printer::from(Inkjet).kind(), PrinterKind::Inkjet);
+	assert_ne!(Printer::Laser(Laser), Printer::Inkjet(Inkjet));
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "proptest")]
+
+use enum_builder::{enum_builder, enum_builder_variant};
+use proptest::prelude::*;
+
+#[enum_builder(proptest)]
+#[derive(Debug)]
+enum Rune {}
+
+#[enum_builder_variant(Rune)]
+#[derive(Debug)]
+struct Glyph(u8);
+
+impl Arbitrary for Glyph {
+	type Parameters = ();
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with(_: ()) -> Self::Strategy {
+		(1..=9u8).prop_map(Glyph).boxed()
+	}
+}
+
+#[enum_builder_variant(Rune)]
+#[derive(Debug)]
+struct Sigil {
+	strokes: u16,
+}
+
+impl Arbitrary for Sigil {
+	type Parameters = ();
+	type Strategy = BoxedStrategy<Self>;
+
+	fn arbitrary_with(_: ()) -> Self::Strategy {
+		(10..20u16).prop_map(|strokes| Sigil { strokes }).boxed()
+	}
+}
+
+proptest! {
+	#[test]
+	fn test_proptest(rune in arbitrary_rune()) {
+		match rune {
+			Rune::Glyph(Glyph(value)) => prop_assert!((1..=9).contains(&value)),
+			Rune::Sigil(Sigil { strokes }) => prop_assert!((10..20).contains(&strokes)),
+		}
+	}
+}
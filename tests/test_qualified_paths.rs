@@ -0,0 +1,26 @@
+mod garden;
+
+use garden::Plant;
+
+#[test]
+fn test_qualified_paths() {
+	let plants = [
+		Plant::Rose(garden::flowers::rose::Rose),
+		Plant::Tulip(garden::flowers::Tulip),
+		Plant::Oak(garden::trees::Oak),
+		Plant::Fern(garden::Fern),
+	];
+
+	assert_eq!(plants.len(), 4);
+}
+
+#[test]
+fn test_reexport() {
+	let plants = [
+		Plant::Rose(garden::Rose),
+		Plant::Tulip(garden::Tulip),
+		Plant::Oak(garden::Oak),
+	];
+
+	assert_eq!(plants.len(), 3);
+}
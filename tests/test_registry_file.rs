@@ -0,0 +1,44 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+mod pigments {
+	#[derive(Debug, PartialEq)]
+	pub struct Ochre;
+
+	#[derive(Debug, PartialEq)]
+	pub struct Umber<const SHADE: u8>;
+}
+
+#[enum_builder(registry_file = "tests/test_registry_file.toml", from, names)]
+#[derive(Debug, PartialEq)]
+enum Pigment {}
+
+#[enum_builder_variant(Pigment)]
+#[derive(Debug, PartialEq)]
+struct Cobalt;
+
+#[test]
+fn test_registry_file() {
+	assert_eq!(
+		Pigment::VARIANT_NAMES,
+		["Cobalt", "Ochre", "Label", "Umber"]
+	);
+	assert_eq!(Pigment::from(pigments::Ochre).name(), "Ochre");
+	assert_eq!(
+		Pigment::from("sienna".to_owned()),
+		Pigment::Label("sienna".to_owned())
+	);
+	assert_eq!(
+		Pigment::from(pigments::Umber::<3>),
+		Pigment::Umber(pigments::Umber)
+	);
+	assert_eq!(Pigment::from(Cobalt).name(), "Cobalt");
+}
+
+#[enum_builder(registry_file = "tests/test_registry_file.toml", files = [], names)]
+enum Glaze {}
+
+#[test]
+fn test_registry_file_only() {
+	assert_eq!(Glaze::VARIANT_NAMES, ["Celadon"]);
+	assert_eq!(Glaze::Celadon(pigments::Ochre).name(), "Celadon");
+}
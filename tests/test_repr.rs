@@ -0,0 +1,76 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(unit, repr = "u8", derive(Clone, Copy))]
+enum Opcode {}
+
+#[enum_builder(repr = "u16", kind)]
+#[allow(dead_code)]
+enum Instruction {}
+
+#[enum_builder_variant(Opcode, discriminant = 1)]
+#[enum_builder_variant(Instruction, discriminant = 10)]
+struct Load;
+
+#[enum_builder_variant(Opcode, discriminant = 7)]
+#[enum_builder_variant(Instruction, discriminant = 20)]
+struct Store;
+
+#[test]
+fn test_repr() {
+	assert_eq!(Opcode::Load as u8, 1);
+	assert_eq!(Opcode::Store as u8, 7);
+	assert_eq!(size_of::<Opcode>(), 1);
+
+	let instruction = Instruction::Store(Store);
+
+	// SAFETY: `repr(u16)` enums store their discriminant as a leading `u16`.
+	let discriminant = unsafe { *(&instruction as *const Instruction as *const u16) };
+
+	assert_eq!(discriminant, 20);
+}
+
+#[test]
+fn test_kind_discriminants() {
+	assert_eq!(Instruction::Store(Store).kind().as_u16(), 20);
+	assert_eq!(InstructionKind::try_from(10), Ok(InstructionKind::Load));
+	assert_eq!(InstructionKind::try_from(11), Err(11));
+}
+
+#[enum_builder(unit, stable_id)]
+enum Register {}
+
+#[enum_builder_variant(Register)]
+#[allow(dead_code)]
+struct Accumulator;
+
+#[enum_builder_variant(Register)]
+#[allow(dead_code)]
+struct Counter;
+
+#[test]
+fn test_stable_id() {
+	// 64-bit FNV-1a hashes of the variant names.
+	assert_eq!(Register::Accumulator.stable_id(), 0x4889f8d699b71699);
+	assert_ne!(
+		Register::Accumulator.stable_id(),
+		Register::Counter.stable_id()
+	);
+}
+
+#[enum_builder(kind)]
+enum Syscall {}
+
+#[enum_builder_variant(Syscall, id = 57)]
+struct Fork;
+
+#[enum_builder_variant(Syscall, id = 59, name = "Execute")]
+struct Exec;
+
+#[test]
+fn test_wire_id() {
+	assert_eq!(Syscall::Fork(Fork).wire_id(), 57);
+	assert_eq!(Syscall::Execute(Exec).wire_id(), 59);
+	assert_eq!(SyscallKind::Execute.wire_id(), 59);
+	assert_eq!(SyscallKind::from_wire_id(57), Some(SyscallKind::Fork));
+	assert_eq!(SyscallKind::from_wire_id(58), None);
+}
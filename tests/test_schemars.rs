@@ -0,0 +1,43 @@
+#![cfg(feature = "schemars")]
+
+use enum_builder::{enum_builder, enum_builder_variant};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[enum_builder(serde(tag = "kind"), schemars)]
+enum Shade {}
+
+#[enum_builder_variant(Shade)]
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Crimson {
+	depth: u8,
+}
+
+#[enum_builder_variant(Shade)]
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Teal {
+	depth: u8,
+}
+
+#[test]
+fn test_schemars() {
+	let _ = [
+		Shade::Crimson(Crimson { depth: 1 }),
+		Shade::Teal(Teal { depth: 2 }),
+	];
+	let schema = serde_json::to_value(schemars::schema_for!(Shade)).unwrap();
+	let variants = schema["oneOf"].as_array().unwrap();
+	let tags: Vec<&str> = variants
+		.iter()
+		.map(|variant| variant["properties"]["kind"]["const"].as_str().unwrap())
+		.collect();
+
+	assert_eq!(tags, ["Crimson", "Teal"]);
+	assert_eq!(
+		variants
+			.iter()
+			.map(|variant| variant["$ref"].as_str().unwrap())
+			.collect::<Vec<_>>(),
+		["#/$defs/Crimson", "#/$defs/Teal"]
+	);
+}
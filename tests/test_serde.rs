@@ -0,0 +1,55 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[enum_builder(serde(tag = "type", content = "data"))]
+#[derive(Debug, PartialEq)]
+enum Beverage {}
+
+#[enum_builder_variant(Beverage)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Espresso {
+	shots: u8,
+}
+
+#[enum_builder_variant(Beverage)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Matcha;
+
+#[test]
+fn test_serde() {
+	let beverage = Beverage::Espresso(Espresso { shots: 2 });
+	let value = serde_json::to_value(&beverage).unwrap();
+
+	assert_eq!(value, json!({"type": "Espresso", "data": {"shots": 2}}));
+	assert_eq!(serde_json::from_value::<Beverage>(value).unwrap(), beverage);
+	assert_eq!(
+		serde_json::from_value::<Beverage>(json!({"type": "Matcha", "data": null})).unwrap(),
+		Beverage::Matcha(Matcha)
+	);
+}
+
+#[enum_builder(serde(tag = "type"), display, rename_all = "kebab-case")]
+#[derive(Debug, PartialEq)]
+enum Endpoint {
+	#[serde(rename = "socket")]
+	UnixSocket,
+}
+
+#[enum_builder_variant(Endpoint)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct HTTPServer;
+
+#[test]
+fn test_serde_rename_all() {
+	let server = Endpoint::HTTPServer(HTTPServer);
+	let value = serde_json::to_value(&server).unwrap();
+
+	assert_eq!(value, json!({"type": "http-server"}));
+	assert_eq!(value["type"], server.to_string());
+	assert_eq!(serde_json::from_value::<Endpoint>(value).unwrap(), server);
+	assert_eq!(
+		serde_json::to_value(Endpoint::UnixSocket).unwrap(),
+		json!({"type": "socket"})
+	);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "serde_factory")]
+
+use enum_builder::{enum_builder, enum_builder_variant};
+use serde::Deserialize;
+use serde_json::json;
+
+#[enum_builder(serde_factory)]
+#[derive(Debug, PartialEq)]
+enum Reagent {}
+
+#[enum_builder_variant(Reagent)]
+#[derive(Debug, PartialEq, Deserialize)]
+struct Acid {
+	ph: u8,
+}
+
+#[enum_builder_variant(Reagent)]
+#[derive(Debug, PartialEq, Deserialize)]
+struct Solvent(String);
+
+#[test]
+fn test_from_tagged_value() {
+	assert_eq!(
+		Reagent::from_tagged_value("Acid", json!({"ph": 2})).unwrap(),
+		Reagent::Acid(Acid { ph: 2 })
+	);
+	assert_eq!(
+		Reagent::from_tagged_value("Solvent", json!("ethanol")).unwrap(),
+		Reagent::Solvent(Solvent("ethanol".to_owned()))
+	);
+	assert!(matches!(
+		Reagent::from_tagged_value("Base", json!(null)),
+		Err(ReagentFactoryError::UnknownTag(tag)) if tag == "Base"
+	));
+	assert!(matches!(
+		Reagent::from_tagged_value("Acid", json!("strong")),
+		Err(ReagentFactoryError::Deserialize(_))
+	));
+}
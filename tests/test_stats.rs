@@ -0,0 +1,50 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(
+	files = ["test_stats.rs"],
+	include = "Wine$",
+	exclude = "^Corked",
+	stats = "target/enum_builder/Cellar.stats"
+)]
+enum Cellar {}
+
+#[enum_builder_variant(Cellar)]
+struct RedWine;
+
+#[enum_builder_variant(Cellar)]
+struct WhiteWine;
+
+#[enum_builder_variant(Cellar)]
+#[allow(dead_code)]
+struct CorkedWine;
+
+#[enum_builder_variant(Cellar)]
+#[allow(dead_code)]
+struct Cider;
+
+#[test]
+fn test_stats() {
+	let _ = [Cellar::RedWine(RedWine), Cellar::WhiteWine(WhiteWine)];
+	let path = concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/target/enum_builder/Cellar.stats"
+	);
+	let report = std::fs::read_to_string(path).unwrap();
+	let (counters, time) = report.rsplit_once("scan time: ").unwrap();
+
+	assert_eq!(
+		counters,
+		concat!(
+			"files walked: 1\n",
+			"directories excluded: 0\n",
+			"files skipped without parsing: 0\n",
+			"files loaded from the cache: 0\n",
+			"files parsed: 1\n",
+			"types discovered: 4\n",
+			"types dropped by only_pub: 0\n",
+			"types dropped by include: 1\n",
+			"types dropped by exclude: 1\n",
+		)
+	);
+	assert!(time.ends_with("ms\n"));
+}
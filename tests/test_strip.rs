@@ -0,0 +1,28 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(strip_prefix = "My", strip_suffix = "Plugin", expect_count = 4)]
+enum Plugin {}
+
+#[enum_builder_variant(Plugin)]
+struct MyAudioPlugin;
+
+#[enum_builder_variant(Plugin)]
+struct VideoPlugin;
+
+#[enum_builder_variant(Plugin)]
+struct Plugin2D;
+
+#[enum_builder_variant(name = "Subtitles", enum = "Plugin")]
+struct MySubtitlePlugin;
+
+#[test]
+fn test_strip() {
+	let plugins = [
+		Plugin::Audio(MyAudioPlugin),
+		Plugin::Video(VideoPlugin),
+		Plugin::Plugin2D(Plugin2D),
+		Plugin::Subtitles(MySubtitlePlugin),
+	];
+
+	assert_eq!(plugins.len(), 4);
+}
@@ -0,0 +1,23 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(kind, strum(IntoStaticStr, EnumCount))]
+enum Chord {}
+
+#[enum_builder_variant(Chord, strum(serialize = "maj"))]
+struct Major;
+
+#[enum_builder_variant(Chord)]
+struct Minor;
+
+#[test]
+fn test_strum() {
+	use strum::EnumCount;
+
+	let name: &'static str = Chord::Major(Major).into();
+
+	assert_eq!(name, "maj");
+	assert_eq!(<&str>::from(Chord::Minor(Minor)), "Minor");
+	assert_eq!(<&str>::from(ChordKind::Major), "maj");
+	assert_eq!(Chord::COUNT, 2);
+	assert_eq!(ChordKind::COUNT, 2);
+}
@@ -0,0 +1,50 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(tags, registry, derive(Debug, PartialEq))]
+enum Vessel {}
+
+#[enum_builder(from = "Vessel", tag = "naval", derive(Debug, PartialEq))]
+enum NavalVessel {}
+
+#[enum_builder_variant(Vessel, tags("naval"))]
+#[derive(Debug, PartialEq)]
+struct Frigate;
+
+#[enum_builder_variant(Vessel, tags("naval", "underwater"))]
+#[derive(Debug, PartialEq)]
+struct Submarine;
+
+#[enum_builder_variant(Vessel)]
+#[derive(Debug, PartialEq)]
+struct Ferry;
+
+#[test]
+fn test_subset() {
+	assert_eq!(
+		Vessel::from(NavalVessel::Frigate(Frigate)),
+		Vessel::Frigate(Frigate)
+	);
+	assert_eq!(
+		NavalVessel::try_from(Vessel::Submarine(Submarine)),
+		Ok(NavalVessel::Submarine(Submarine))
+	);
+	assert_eq!(
+		NavalVessel::try_from(Vessel::Ferry(Ferry)),
+		Err(Vessel::Ferry(Ferry))
+	);
+}
+
+#[test]
+fn test_tags() {
+	assert_eq!(Vessel::Frigate(Frigate).tags(), ["naval"]);
+	assert_eq!(Vessel::Submarine(Submarine).tags(), ["naval", "underwater"]);
+	assert!(Vessel::Ferry(Ferry).tags().is_empty());
+
+	let underwater: Vec<&str> = VESSEL_REGISTRY
+		.iter()
+		.filter(|descriptor| descriptor.tags.contains(&"underwater"))
+		.map(|descriptor| descriptor.name)
+		.collect();
+
+	assert_eq!(underwater, ["Submarine"]);
+}
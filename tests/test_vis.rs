@@ -0,0 +1,29 @@
+mod aviary;
+
+use aviary::{Beetle, Bird, Insect, Parrot};
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[test]
+fn test_vis() {
+	let bird = Bird::Parrot(Parrot);
+	let insect = Insect::Beetle(Beetle);
+
+	assert!(matches!(bird, Bird::Parrot(_)));
+	assert!(matches!(insect, Insect::Beetle(_)));
+}
+
+#[enum_builder(only_pub, names)]
+pub enum Probe {}
+
+#[enum_builder_variant(Probe)]
+pub struct Hygrometer;
+
+#[enum_builder_variant(Probe)]
+#[allow(dead_code)]
+struct TemplateProbe;
+
+#[test]
+fn test_only_pub() {
+	assert_eq!(Probe::VARIANT_NAMES, ["Hygrometer"]);
+	assert_eq!(Probe::Hygrometer(Hygrometer).name(), "Hygrometer");
+}
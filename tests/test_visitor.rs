@@ -0,0 +1,49 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+#[enum_builder(visitor)]
+enum Node {
+	Empty,
+	Pair(u8, u8),
+	Named { id: u32 },
+}
+
+#[enum_builder_variant(Node)]
+struct Leaf(u32);
+
+#[derive(Default)]
+struct Summer {
+	total: u32,
+}
+
+impl NodeVisitor for Summer {
+	fn visit_empty(&mut self) {}
+
+	fn visit_pair(&mut self, first: &u8, second: &u8) {
+		self.total += u32::from(*first) + u32::from(*second);
+	}
+
+	fn visit_named(&mut self, id: &u32) {
+		self.total += id;
+	}
+
+	fn visit_leaf(&mut self, leaf: &Leaf) {
+		self.total += leaf.0;
+	}
+}
+
+#[test]
+fn test_visitor() {
+	let nodes = [
+		Node::Empty,
+		Node::Pair(1, 2),
+		Node::Named { id: 3 },
+		Node::Leaf(Leaf(4)),
+	];
+	let mut summer = Summer::default();
+
+	for node in &nodes {
+		node.accept(&mut summer);
+	}
+
+	assert_eq!(summer.total, 10);
+}
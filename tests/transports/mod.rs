@@ -0,0 +1,15 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+use super::Tcp;
+
+#[enum_builder(path = "..", registry, from_name, module_path)]
+#[derive(Debug, PartialEq)]
+pub enum Transport {}
+
+#[enum_builder_variant(Transport)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Quic;
+
+#[enum_builder_variant(Transport)]
+#[derive(Debug, PartialEq)]
+pub struct Serial(pub u32);
@@ -0,0 +1,39 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+pub mod power;
+
+pub trait Use {
+	fn noise(&self) -> &'static str;
+}
+
+#[enum_builder(
+	mod_name = "implement_gen",
+	qualified_paths,
+	dispatch = "Use",
+	kind,
+	registry,
+	module_path,
+	derive(Debug, PartialEq)
+)]
+pub enum Implement {}
+
+#[enum_builder(mod_name = "bench_gen", group_by_module, qualified_paths, names)]
+enum Bench {}
+
+#[enum_builder_variant(Implement)]
+#[enum_builder_variant(Bench)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Chisel;
+
+impl Use for Chisel {
+	fn noise(&self) -> &'static str {
+		"tap"
+	}
+}
+
+pub fn bench_names() -> [&'static str; 2] {
+	[
+		Bench::Chisel(Chisel).name(),
+		Bench::from(power::Lathe).name(),
+	]
+}
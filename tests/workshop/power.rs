@@ -0,0 +1,14 @@
+use enum_builder::enum_builder_variant;
+
+use super::Use;
+
+#[enum_builder_variant(Implement)]
+#[enum_builder_variant(Bench)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Lathe;
+
+impl Use for Lathe {
+	fn noise(&self) -> &'static str {
+		"whirr"
+	}
+}
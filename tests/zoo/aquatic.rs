@@ -0,0 +1,10 @@
+use enum_builder::enum_builder_variant;
+
+/// Eaten by the struct Shark declared below.
+#[enum_builder_variant(Creature)]
+#[derive(Debug, PartialEq)]
+pub struct Fish;
+
+#[enum_builder_variant(Creature)]
+#[derive(Debug, PartialEq)]
+pub struct Shark;
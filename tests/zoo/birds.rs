@@ -0,0 +1,5 @@
+use enum_builder::enum_builder_variant;
+
+#[enum_builder_variant(Creature)]
+#[derive(Debug, PartialEq)]
+pub struct Eagle;
@@ -0,0 +1,18 @@
+use enum_builder::{enum_builder, enum_builder_variant};
+
+pub mod aquatic;
+pub mod birds;
+
+#[enum_builder(
+	group_by_module,
+	qualified_paths,
+	module_path,
+	source_location,
+	type_name,
+	derive(Debug, PartialEq)
+)]
+pub enum Creature {}
+
+#[enum_builder_variant(Creature)]
+#[derive(Debug, PartialEq)]
+pub struct Lion;
@@ -0,0 +1,8 @@
+use enum_builder::{enum_builder_check, enum_builder_variant};
+
+#[enum_builder_variant(Aminal)]
+struct Dog;
+
+enum_builder_check!();
+
+fn main() {}